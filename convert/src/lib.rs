@@ -0,0 +1,233 @@
+//! Conversion of images to columns for [`is31fl3728-rs`](https://crates.io/crates/is31fl3728-rs).
+//!
+//! Images are converted on the host, so firmware gets ready columns for `draw`
+//! or a `SpriteSheet` for `Animation` without decoding images at runtime.
+
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use image::codecs::gif::GifDecoder;
+use image::{AnimationDecoder, ImageError, RgbaImage};
+use is31fl3728_rs::animation::SPRITE_SHEET_MAGIC;
+use is31fl3728_rs::{MatrixDimensions, Orientation};
+
+/// Duration of frames of still images.
+pub const DEFAULT_DURATION_MS: u16 = 100;
+
+#[derive(Debug)]
+pub enum ConvertError {
+    Image(ImageError),
+    /// Size of the image doesn't match the matrix: actual and expected width and height.
+    WrongSize((u32, u32), (u8, u8)),
+    /// Sprite sheet has at most 255 frames.
+    TooManyFrames(usize),
+    /// Width of the strip isn't a multiple of the picture width: widths of the strip and the picture.
+    WrongStripWidth(u32, u32),
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConvertError::Image(error) => write!(f, "can't read image: {error}"),
+            ConvertError::WrongSize((width, height), (expected_width, expected_height)) => write!(
+                f,
+                "image is {width}x{height}, the matrix needs {expected_width}x{expected_height}"
+            ),
+            ConvertError::TooManyFrames(count) => {
+                write!(f, "{count} frames, sprite sheet holds at most 255")
+            }
+            ConvertError::WrongStripWidth(strip_width, width) => write!(
+                f,
+                "strip is {strip_width} pixels wide, it isn't a multiple of the picture width {width}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+impl From<ImageError> for ConvertError {
+    fn from(error: ImageError) -> Self {
+        ConvertError::Image(error)
+    }
+}
+
+/// How pixels of images become "on" and "off" LEDs.
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    pub dimensions: MatrixDimensions,
+    /// Pixels with brightness (0-255) from the threshold are "on".
+    pub threshold: u8,
+    /// Use Floyd-Steinberg dithering instead of the plain threshold.
+    pub dither: bool,
+    /// Dark pixels are "on".
+    pub invert: bool,
+    /// How the matrix is mounted, columns are rotated for the driver with `Orientation::Rotate0`.
+    pub orientation: Orientation,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            dimensions: MatrixDimensions::M8x8,
+            threshold: 128,
+            dither: false,
+            invert: false,
+            orientation: Orientation::Rotate0,
+        }
+    }
+}
+
+impl Options {
+    /// Width and height of the picture according to the orientation.
+    pub fn size(&self) -> (u8, u8) {
+        self.orientation.size(
+            self.dimensions.columns_count(),
+            self.dimensions.rows_count(),
+        )
+    }
+}
+
+/// Columns of one picture and its duration.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Frame {
+    pub columns: Vec<u8>,
+    pub duration_ms: u16,
+}
+
+/// Read frames of the image: all frames of GIF, one frame of other formats.
+pub fn load(path: &Path) -> Result<Vec<(RgbaImage, u16)>, ConvertError> {
+    let is_gif = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("gif"));
+    if !is_gif {
+        return Ok(vec![(image::open(path)?.to_rgba8(), DEFAULT_DURATION_MS)]);
+    }
+
+    let file = File::open(path).map_err(ImageError::IoError)?;
+    let frames = GifDecoder::new(BufReader::new(file))?
+        .into_frames()
+        .collect_frames()?;
+    Ok(frames
+        .into_iter()
+        .map(|frame| {
+            let (numerator, denominator) = frame.delay().numer_denom_ms();
+            let duration_ms = (numerator / denominator.max(1)).min(u16::MAX as u32) as u16;
+            (frame.into_buffer(), duration_ms)
+        })
+        .collect())
+}
+
+/// Split a horizontal strip of pictures (like Aseprite exports) to pictures of the width.
+/// The strip must consist of whole pictures.
+pub fn split_strip(image: &RgbaImage, width: u32) -> Result<Vec<RgbaImage>, ConvertError> {
+    if width == 0 || !image.width().is_multiple_of(width) {
+        return Err(ConvertError::WrongStripWidth(image.width(), width));
+    }
+    Ok((0..image.width() / width)
+        .map(|idx| {
+            image::imageops::crop_imm(image, idx * width, 0, width, image.height()).to_image()
+        })
+        .collect())
+}
+
+/// Convert the picture to columns for `draw`.
+pub fn convert(image: &RgbaImage, options: &Options) -> Result<Vec<u8>, ConvertError> {
+    let (width, height) = options.size();
+    if image.dimensions() != (width as u32, height as u32) {
+        return Err(ConvertError::WrongSize(image.dimensions(), (width, height)));
+    }
+
+    let mut brightness: Vec<f32> = image
+        .pixels()
+        .map(|pixel| {
+            let [red, green, blue, alpha] = pixel.0.map(f32::from);
+            // transparent pixels are black
+            let luma = (0.299 * red + 0.587 * green + 0.114 * blue) * alpha / 255.0;
+            if options.invert {
+                255.0 - luma
+            } else {
+                luma
+            }
+        })
+        .collect();
+
+    let columns_count = options.dimensions.columns_count();
+    let rows_count = options.dimensions.rows_count();
+    let mut columns = vec![0u8; columns_count as usize];
+    let threshold = options.threshold as f32;
+    let width = width as usize;
+    for y in 0..height as usize {
+        for x in 0..width {
+            let idx = y * width + x;
+            let on = brightness[idx] >= threshold;
+            if options.dither {
+                let error = brightness[idx] - if on { 255.0 } else { 0.0 };
+                diffuse(&mut brightness, width, x, y, error);
+            }
+            if !on {
+                continue;
+            }
+            let point = options
+                .orientation
+                .to_matrix(x as u8, y as u8, columns_count, rows_count);
+            if let Some((column, row)) = point {
+                columns[column as usize] |= options.dimensions.row_mask(row).unwrap_or(0);
+            }
+        }
+    }
+    Ok(columns)
+}
+
+/// Spread the quantization error to neighbours by Floyd-Steinberg weights.
+fn diffuse(brightness: &mut [f32], width: usize, x: usize, y: usize, error: f32) {
+    let height = brightness.len() / width;
+    let neighbours = [(1, 0, 7.0), (-1, 1, 3.0), (0, 1, 5.0), (1, 1, 1.0)];
+    for (dx, dy, weight) in neighbours {
+        let (nx, ny) = (x as isize + dx, y + dy);
+        if nx < 0 || nx as usize >= width || ny >= height {
+            continue;
+        }
+        brightness[ny * width + nx as usize] += error * weight / 16.0;
+    }
+}
+
+/// Rust source with the constant array of frames.
+pub fn to_rust(name: &str, frames: &[Frame], options: &Options) -> String {
+    let columns_count = options.dimensions.columns_count();
+    let mut source = format!(
+        "// Generated by is31fl3728-convert, {:?}, {:?}\n",
+        options.dimensions, options.orientation
+    );
+    source += &format!(
+        "pub const {name}: [[u8; {columns_count}]; {}] = [\n",
+        frames.len()
+    );
+    for frame in frames {
+        let columns: Vec<String> = frame
+            .columns
+            .iter()
+            .map(|column| format!("0b{column:08b}"))
+            .collect();
+        source += &format!("    [{}],\n", columns.join(", "));
+    }
+    source += "];\n";
+    source
+}
+
+/// Sprite sheet for `SpriteSheet::parse`, frames keep the current intensity.
+pub fn to_sprite_sheet(frames: &[Frame], options: &Options) -> Result<Vec<u8>, ConvertError> {
+    let frames_count =
+        u8::try_from(frames.len()).map_err(|_| ConvertError::TooManyFrames(frames.len()))?;
+    let mut data = SPRITE_SHEET_MAGIC.to_vec();
+    data.push(options.dimensions.columns_count());
+    data.push(frames_count);
+    for frame in frames {
+        data.extend_from_slice(&frame.duration_ms.to_le_bytes());
+        data.push(0);
+        data.extend_from_slice(&frame.columns);
+    }
+    Ok(data)
+}
@@ -0,0 +1,172 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::{env, fs, io};
+
+use io::Write;
+use is31fl3728_convert::{convert, load, split_strip, to_rust, to_sprite_sheet, Frame, Options};
+use is31fl3728_rs::{MatrixDimensions, Orientation};
+
+const USAGE: &str = "\
+Convert BMP/PNG/GIF images to columns for is31fl3728-rs.
+
+Usage: is31fl3728-convert [OPTIONS] <IMAGE>...
+
+Each image is a frame, all frames of GIF are used.
+
+Options:
+  -d, --dimensions <8x8|7x9|6x10|5x11>  array mode of the chip [default: 8x8]
+  -t, --threshold <0-255>               pixels from this brightness are on [default: 128]
+      --dither                          use Floyd-Steinberg dithering
+      --invert                          dark pixels are on
+  -r, --orientation <rotate0|rotate90|rotate180|rotate270|mirror-horizontal|mirror-vertical>
+                                        how the matrix is mounted [default: rotate0]
+      --strip                           split each image to frames of the matrix width
+      --duration <MS>                   duration of frames [default: GIF delays or 100]
+  -f, --format <rust|sprite-sheet>      output format [default: rust]
+  -n, --name <NAME>                     name of the Rust constant [default: FRAMES]
+  -o, --output <FILE>                   output file, required for sprite-sheet [default: stdout]
+  -h, --help                            print this help
+";
+
+struct Args {
+    options: Options,
+    inputs: Vec<PathBuf>,
+    strip: bool,
+    duration_ms: Option<u16>,
+    sprite_sheet: bool,
+    name: String,
+    output: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<Option<Args>, String> {
+    let mut args = Args {
+        options: Options::default(),
+        inputs: Vec::new(),
+        strip: false,
+        duration_ms: None,
+        sprite_sheet: false,
+        name: "FRAMES".into(),
+        output: None,
+    };
+
+    let mut raw = env::args().skip(1);
+    while let Some(arg) = raw.next() {
+        let mut value = || raw.next().ok_or(format!("{arg} requires a value"));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-d" | "--dimensions" => {
+                args.options.dimensions = match value()?.as_str() {
+                    "8x8" => MatrixDimensions::M8x8,
+                    "7x9" => MatrixDimensions::M7x9,
+                    "6x10" => MatrixDimensions::M6x10,
+                    "5x11" => MatrixDimensions::M5x11,
+                    other => return Err(format!("unknown dimensions {other}")),
+                }
+            }
+            "-t" | "--threshold" => {
+                let threshold = value()?;
+                args.options.threshold = threshold
+                    .parse()
+                    .map_err(|_| format!("threshold {threshold} is not 0-255"))?;
+            }
+            "--dither" => args.options.dither = true,
+            "--invert" => args.options.invert = true,
+            "-r" | "--orientation" => {
+                args.options.orientation = match value()?.as_str() {
+                    "rotate0" => Orientation::Rotate0,
+                    "rotate90" => Orientation::Rotate90,
+                    "rotate180" => Orientation::Rotate180,
+                    "rotate270" => Orientation::Rotate270,
+                    "mirror-horizontal" => Orientation::MirrorHorizontal,
+                    "mirror-vertical" => Orientation::MirrorVertical,
+                    other => return Err(format!("unknown orientation {other}")),
+                }
+            }
+            "--strip" => args.strip = true,
+            "--duration" => {
+                let duration = value()?;
+                args.duration_ms = Some(
+                    duration
+                        .parse()
+                        .map_err(|_| format!("duration {duration} is not 0-65535"))?,
+                );
+            }
+            "-f" | "--format" => {
+                args.sprite_sheet = match value()?.as_str() {
+                    "rust" => false,
+                    "sprite-sheet" => true,
+                    other => return Err(format!("unknown format {other}")),
+                }
+            }
+            "-n" | "--name" => args.name = value()?,
+            "-o" | "--output" => args.output = Some(value()?.into()),
+            _ if arg.starts_with('-') => return Err(format!("unknown option {arg}")),
+            _ => args.inputs.push(arg.into()),
+        }
+    }
+
+    if args.inputs.is_empty() {
+        return Err("no images".into());
+    }
+    if args.sprite_sheet && args.output.is_none() {
+        return Err("sprite-sheet format requires --output".into());
+    }
+    Ok(Some(args))
+}
+
+fn run(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let (width, _) = args.options.size();
+    let mut frames = Vec::new();
+    for input in &args.inputs {
+        for (image, duration_ms) in load(input)? {
+            let duration_ms = args.duration_ms.unwrap_or(duration_ms);
+            let images = if args.strip {
+                split_strip(&image, width as u32)
+                    .map_err(|error| format!("{}: {error}", input.display()))?
+            } else {
+                vec![image]
+            };
+            for image in images {
+                let columns = convert(&image, &args.options)
+                    .map_err(|error| format!("{}: {error}", input.display()))?;
+                frames.push(Frame {
+                    columns,
+                    duration_ms,
+                });
+            }
+        }
+    }
+
+    let output = if args.sprite_sheet {
+        to_sprite_sheet(&frames, &args.options)?
+    } else {
+        to_rust(&args.name, &frames, &args.options).into_bytes()
+    };
+    match &args.output {
+        Some(path) => fs::write(path, output)?,
+        None => io::stdout().write_all(&output)?,
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
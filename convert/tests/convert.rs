@@ -0,0 +1,106 @@
+use image::{Rgba, RgbaImage};
+use is31fl3728_convert::{convert, split_strip, to_sprite_sheet, ConvertError, Frame, Options};
+use is31fl3728_rs::{MatrixDimensions, Orientation, SpriteSheet};
+
+const HEART: [&str; 8] = [
+    "........", ".##..##.", "########", "########", "########", ".######.", "..####..", "...##...",
+];
+
+const HEART_COLUMNS: [u8; 8] = [
+    0b0011_1000,
+    0b0111_1100,
+    0b0111_1110,
+    0b0011_1111,
+    0b0011_1111,
+    0b0111_1110,
+    0b0111_1100,
+    0b0011_1000,
+];
+
+fn image(rows: &[&str]) -> RgbaImage {
+    RgbaImage::from_fn(rows[0].len() as u32, rows.len() as u32, |x, y| {
+        match rows[y as usize].as_bytes()[x as usize] {
+            b'#' => Rgba([255, 255, 255, 255]),
+            _ => Rgba([0, 0, 0, 255]),
+        }
+    })
+}
+
+#[test]
+fn threshold_and_orientation() {
+    let options = Options::default();
+    assert_eq!(convert(&image(&HEART), &options).unwrap(), HEART_COLUMNS);
+
+    let options = Options {
+        orientation: Orientation::Rotate180,
+        ..options
+    };
+    let expected: Vec<u8> = HEART_COLUMNS
+        .iter()
+        .rev()
+        .map(|column| column.reverse_bits())
+        .collect();
+    assert_eq!(convert(&image(&HEART), &options).unwrap(), expected);
+}
+
+#[test]
+fn dithering_of_gray() {
+    let gray = RgbaImage::from_pixel(8, 8, Rgba([128, 128, 128, 255]));
+    let options = Options {
+        threshold: 128,
+        dither: true,
+        ..Options::default()
+    };
+    let columns = convert(&gray, &options).unwrap();
+    let on: u32 = columns.iter().map(|column| column.count_ones()).sum();
+    assert_eq!(on, 32);
+}
+
+#[test]
+fn wrong_size() {
+    let options = Options {
+        dimensions: MatrixDimensions::M5x11,
+        ..Options::default()
+    };
+    assert!(matches!(
+        convert(&image(&HEART), &options),
+        Err(ConvertError::WrongSize((8, 8), (11, 5)))
+    ));
+}
+
+#[test]
+fn strip_of_partial_pictures() {
+    let strip: Vec<String> = HEART.iter().map(|row| format!("{row}...")).collect();
+    let strip: Vec<&str> = strip.iter().map(String::as_str).collect();
+    assert!(matches!(
+        split_strip(&image(&strip), 8),
+        Err(ConvertError::WrongStripWidth(11, 8))
+    ));
+    assert!(matches!(
+        split_strip(&image(&HEART), 0),
+        Err(ConvertError::WrongStripWidth(8, 0))
+    ));
+}
+
+#[test]
+fn strip_to_sprite_sheet() {
+    let strip: Vec<String> = HEART.iter().map(|row| format!("{row}{row}")).collect();
+    let strip: Vec<&str> = strip.iter().map(String::as_str).collect();
+    let options = Options::default();
+    let frames: Vec<Frame> = split_strip(&image(&strip), 8)
+        .unwrap()
+        .iter()
+        .map(|image| Frame {
+            columns: convert(image, &options).unwrap(),
+            duration_ms: 300,
+        })
+        .collect();
+
+    let data = to_sprite_sheet(&frames, &options).unwrap();
+    let sheet = SpriteSheet::parse(&data).unwrap();
+    assert_eq!(sheet.len(), 2);
+    let frame = sheet.frame(1).unwrap();
+    assert_eq!(frame.columns, HEART_COLUMNS);
+    assert_eq!(frame.duration_ms, 300);
+    assert_eq!(frame.intensity, None);
+}
@@ -3,7 +3,7 @@
 #![no_std]
 
 
-use is31fl3728_rs::{LightingIntensity, MatrixDimensions, IS31FL3728};
+use is31fl3728_rs::{Address, LightingIntensity, MatrixDimensions, IS31FL3728};
 use tinybmp::Bmp;
 use embedded_graphics::{image::Image, pixelcolor::BinaryColor, prelude::*};
 
@@ -39,7 +39,8 @@ fn main() -> ! {
         &clocks,
     );
 
-    let matrix_addr: u8 = 0x60;
+    // AD pin is connected to GND
+    let matrix_addr = Address::Gnd;
     let mut led_matrix = IS31FL3728::new(i2c1, matrix_addr, MatrixDimensions::M8x8, false).unwrap();
 
 
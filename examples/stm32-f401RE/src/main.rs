@@ -3,7 +3,7 @@
 #![no_std]
 
 
-use is31fl3728_rs::{LightingIntensity, MatrixDimensions, DEFAULT_LIGHTING_INTENSITY, IS31FL3728};
+use is31fl3728_rs::{Address, LightingIntensity, MatrixDimensions, DEFAULT_LIGHTING_INTENSITY, IS31FL3728};
 
 // Halt on panic
 use panic_rtt_target as _;
@@ -37,7 +37,8 @@ fn main() -> ! {
         &clocks,
     );
 
-    let matrix_addr: u8 = 0x60;
+    // AD pin is connected to GND
+    let matrix_addr = Address::Gnd;
     let mut led_matrix = IS31FL3728::new(i2c1, matrix_addr, MatrixDimensions::M8x8, false).unwrap();
 
     led_matrix.clear().unwrap();
@@ -0,0 +1,38 @@
+//! Scrolling text on the emulated 5x11 matrix in a terminal.
+//!
+//! `cargo run --example terminal --features emulator,font`
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use is31fl3728_rs::emulator::Emulator;
+use is31fl3728_rs::marquee::Direction;
+use is31fl3728_rs::terminal::{Renderer, Style};
+use is31fl3728_rs::{Address, Marquee, MatrixDimensions, IS31FL3728};
+
+fn main() {
+    let emulator = Emulator::new(0x60);
+    let mut led_matrix = IS31FL3728::new(
+        emulator.clone(),
+        Address::Gnd,
+        MatrixDimensions::M5x11,
+        false,
+    )
+    .unwrap();
+
+    let mut marquee: Marquee<128> = Marquee::new("Hello, IS31FL3728!", led_matrix.font())
+        .unwrap()
+        .direction(Direction::Left)
+        .step_period(80);
+    let mut renderer = Renderer::new(Style::Blocks).live(true);
+
+    let start = Instant::now();
+    loop {
+        let now_ms = start.elapsed().as_millis() as u32;
+        if !led_matrix.marquee_tick(&mut marquee, now_ms).unwrap() {
+            break;
+        }
+        renderer.show(&emulator).unwrap();
+        thread::sleep(Duration::from_millis(20));
+    }
+}
@@ -0,0 +1,158 @@
+//! Compile-time macros for [`is31fl3728-rs`](https://crates.io/crates/is31fl3728-rs).
+//!
+//! Use them with the `macros` feature of the driver crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Error, LitStr, Path, Token};
+
+/// Characters of "on" pixels.
+const ON: [char; 4] = ['#', 'X', '*', '1'];
+/// Characters of "off" pixels.
+const OFF: [char; 4] = ['.', ' ', '_', '0'];
+
+struct Matrix {
+    dimensions: Path,
+    rows: Punctuated<LitStr, Token![,]>,
+}
+
+impl Parse for Matrix {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let dimensions = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let rows = Punctuated::parse_terminated(input)?;
+        Ok(Matrix { dimensions, rows })
+    }
+}
+
+/// Rows and columns of the array mode.
+fn dimensions(path: &Path) -> syn::Result<(usize, usize)> {
+    let name = path
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default();
+    match name.as_str() {
+        "M8x8" => Ok((8, 8)),
+        "M7x9" => Ok((7, 9)),
+        "M6x10" => Ok((6, 10)),
+        "M5x11" => Ok((5, 11)),
+        _ => Err(Error::new_spanned(
+            path,
+            "expected matrix dimensions: M8x8, M7x9, M6x10 or M5x11",
+        )),
+    }
+}
+
+fn columns(matrix: &Matrix) -> syn::Result<Vec<u8>> {
+    let (rows_count, columns_count) = dimensions(&matrix.dimensions)?;
+    if matrix.rows.len() != rows_count {
+        let span = matrix
+            .rows
+            .last()
+            .map(LitStr::span)
+            .unwrap_or_else(Span::call_site);
+        return Err(Error::new(
+            span,
+            format!(
+                "{} rows are given, the matrix has {} rows",
+                matrix.rows.len(),
+                rows_count
+            ),
+        ));
+    }
+
+    let mut columns = vec![0u8; columns_count];
+    for (row_idx, row) in matrix.rows.iter().enumerate() {
+        let pixels: Vec<char> = row.value().chars().collect();
+        if pixels.len() != columns_count {
+            return Err(Error::new(
+                row.span(),
+                format!(
+                    "row {} has {} pixels, the matrix has {} columns",
+                    row_idx + 1,
+                    pixels.len(),
+                    columns_count
+                ),
+            ));
+        }
+        for (column, pixel) in columns.iter_mut().zip(pixels) {
+            if ON.contains(&pixel) {
+                // the chip ignores older bits when there are less than 8 rows
+                *column |= 1 << (rows_count - 1 - row_idx);
+            } else if !OFF.contains(&pixel) {
+                return Err(Error::new(
+                    row.span(),
+                    format!(
+                        "illegal character {:?} in row {}, use one of {:?} for \"on\" and one of {:?} for \"off\"",
+                        pixel,
+                        row_idx + 1,
+                        ON,
+                        OFF
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(columns)
+}
+
+/// Convert ASCII-art rows to the array of columns for `draw`.
+///
+/// The first argument is the array mode (`M8x8`, `M7x9`, `M6x10`, `M5x11`, optionally with a path),
+/// then one string per row. `#`, `X`, `*` and `1` are "on" pixels, `.`, space, `_` and `0` are "off".
+/// ```
+/// use is31fl3728_macros::matrix;
+///
+/// const HEART: [u8; 8] = matrix!(M8x8,
+///     "........",
+///     ".##..##.",
+///     "########",
+///     "########",
+///     "########",
+///     ".######.",
+///     "..####..",
+///     "...##...",
+/// );
+/// assert_eq!(HEART[0], 0b0011_1000);
+/// ```
+///
+/// Rows of a wrong width, a wrong count of rows and other characters are compile errors:
+/// ```compile_fail
+/// use is31fl3728_macros::matrix;
+///
+/// let picture = matrix!(M5x11,
+///     "#.........#",
+///     ".#.......#",
+///     "..#.....#..",
+///     "...#...#...",
+///     "....#.#....",
+/// );
+/// ```
+/// ```compile_fail
+/// use is31fl3728_macros::matrix;
+///
+/// let picture = matrix!(M5x11,
+///     "#.........#",
+///     ".#.......#.",
+///     "..#.....#..",
+///     "...#...#...",
+///     "....o.o....",
+/// );
+/// ```
+/// ```compile_fail
+/// use is31fl3728_macros::matrix;
+///
+/// let picture = matrix!(M8x8, "########");
+/// ```
+#[proc_macro]
+pub fn matrix(input: TokenStream) -> TokenStream {
+    let matrix = parse_macro_input!(input as Matrix);
+    match columns(&matrix) {
+        Ok(columns) => quote!([#(#columns),*]).into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
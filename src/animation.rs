@@ -0,0 +1,297 @@
+//! Frame animations.
+//!
+//! [`Animation`] plays frames with their own durations, the driver shows them:
+//! call `animation_tick` with the current time from a timer or main loop.
+//! Frames are defined in code or loaded from a [`SpriteSheet`]:
+//! ```no_run
+//! # use is31fl3728_rs::{Address, Animation, MatrixDimensions, PlayMode, SpriteSheet, IS31FL3728};
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! # let mut led_matrix = IS31FL3728::new(i2c, Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+//! # fn millis() -> u32 { 0 }
+//! // static HEART: &[u8] = include_bytes!("heart.anim");
+//! # static HEART: &[u8] = &[];
+//! let sheet = SpriteSheet::parse(HEART).unwrap();
+//! let mut animation = Animation::from_sprite_sheet(sheet).mode(PlayMode::PingPong);
+//! loop {
+//!     led_matrix.animation_tick(&mut animation, millis()).unwrap();
+//! }
+//! ```
+
+use crate::LightingIntensity;
+
+/// One picture of the animation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Frame<'a> {
+    /// Columns of the picture like for `draw`.
+    pub columns: &'a [u8],
+    pub duration_ms: u16,
+    /// Intensity which is set before the frame is shown, `None` keeps the current one.
+    pub intensity: Option<LightingIntensity>,
+}
+
+impl<'a> Frame<'a> {
+    pub const fn new(columns: &'a [u8], duration_ms: u16) -> Self {
+        Frame {
+            columns,
+            duration_ms,
+            intensity: None,
+        }
+    }
+
+    pub const fn with_intensity(self, intensity: LightingIntensity) -> Self {
+        Frame {
+            intensity: Some(intensity),
+            ..self
+        }
+    }
+}
+
+/// Errors of sprite sheet parsing.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SpriteSheetError {
+    /// Data doesn't start with `SPRITE_SHEET_MAGIC` and the supported version.
+    InvalidHeader,
+    /// Count of columns is 0 or more than `MAX_COLUMNS`.
+    InvalidColumnsCount(u8),
+    /// Intensity of the frame is not 0 and is not one of supported currents.
+    InvalidIntensity(u8),
+    /// Length of data doesn't match the count of frames.
+    InvalidLength,
+}
+
+impl core::fmt::Display for SpriteSheetError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "invalid sprite sheet header"),
+            Self::InvalidColumnsCount(count) => write!(f, "invalid count of columns {}", count),
+            Self::InvalidIntensity(milliamps) => write!(f, "invalid intensity {}mA", milliamps),
+            Self::InvalidLength => write!(f, "length doesn't match the count of frames"),
+        }
+    }
+}
+
+impl core::error::Error for SpriteSheetError {}
+
+/// The first bytes of a sprite sheet: magic and version of the format.
+pub const SPRITE_SHEET_MAGIC: [u8; 3] = *b"IS1";
+
+/// Compact binary format of frames, suitable for `include_bytes!`.
+///
+/// Layout, multi-byte numbers are little endian:
+/// * `SPRITE_SHEET_MAGIC`, 3 bytes
+/// * count of columns of each frame, 1 byte
+/// * count of frames, 1 byte
+/// * frames, each one is: duration in milliseconds (2 bytes), intensity in milliamps
+///   (1 byte, 0 keeps the current intensity), columns of the picture
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SpriteSheet<'a> {
+    columns_count: u8,
+    frames_count: u8,
+    data: &'a [u8],
+}
+
+const HEADER_LEN: usize = 5;
+const FRAME_HEADER_LEN: usize = 3;
+
+impl<'a> SpriteSheet<'a> {
+    /// Check the header and the length of the data.
+    pub fn parse(data: &'a [u8]) -> Result<Self, SpriteSheetError> {
+        if data.len() < HEADER_LEN || data[..3] != SPRITE_SHEET_MAGIC {
+            return Err(SpriteSheetError::InvalidHeader);
+        }
+        let columns_count = data[3];
+        let frames_count = data[4];
+        if columns_count == 0 || columns_count as usize > crate::MAX_COLUMNS {
+            return Err(SpriteSheetError::InvalidColumnsCount(columns_count));
+        }
+        let frame_len = FRAME_HEADER_LEN + columns_count as usize;
+        if data.len() != HEADER_LEN + frame_len * frames_count as usize {
+            return Err(SpriteSheetError::InvalidLength);
+        }
+
+        let sheet = SpriteSheet {
+            columns_count,
+            frames_count,
+            data: &data[HEADER_LEN..],
+        };
+        for chunk in sheet.data.chunks(frame_len) {
+            let milliamps = chunk[2];
+            if milliamps != 0 && (milliamps % 5 != 0 || milliamps > 75) {
+                return Err(SpriteSheetError::InvalidIntensity(milliamps));
+            }
+        }
+        Ok(sheet)
+    }
+
+    pub fn columns_count(&self) -> u8 {
+        self.columns_count
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames_count as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames_count == 0
+    }
+
+    /// Frame by index, `None` if there is no such frame.
+    pub fn frame(&self, idx: usize) -> Option<Frame<'a>> {
+        if idx >= self.len() {
+            return None;
+        }
+        let frame_len = FRAME_HEADER_LEN + self.columns_count as usize;
+        let chunk = &self.data[idx * frame_len..(idx + 1) * frame_len];
+        let milliamps = chunk[2];
+        Some(Frame {
+            columns: &chunk[FRAME_HEADER_LEN..],
+            duration_ms: u16::from_le_bytes([chunk[0], chunk[1]]),
+            intensity: (milliamps != 0).then(|| LightingIntensity::from_milliamps(milliamps)),
+        })
+    }
+}
+
+/// How the animation is repeated.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PlayMode {
+    /// Play all frames and stop on the last one.
+    Once,
+    /// Start from the first frame after the last one.
+    Loop,
+    /// Play frames forward and backward.
+    PingPong,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Frames<'a> {
+    Slice(&'a [Frame<'a>]),
+    SpriteSheet(SpriteSheet<'a>),
+}
+
+/// Player of frames.
+#[derive(Clone, Copy, Debug)]
+pub struct Animation<'a> {
+    frames: Frames<'a>,
+    mode: PlayMode,
+    current: usize,
+    forward: bool,
+    frame_start_ms: Option<u32>,
+    finished: bool,
+}
+
+impl<'a> Animation<'a> {
+    /// Animation of frames which plays once.
+    pub fn new(frames: &'a [Frame<'a>]) -> Self {
+        Self::with_frames(Frames::Slice(frames))
+    }
+
+    /// Animation of frames of the sprite sheet which plays once.
+    pub fn from_sprite_sheet(sheet: SpriteSheet<'a>) -> Self {
+        Self::with_frames(Frames::SpriteSheet(sheet))
+    }
+
+    fn with_frames(frames: Frames<'a>) -> Self {
+        Animation {
+            frames,
+            mode: PlayMode::Once,
+            current: 0,
+            forward: true,
+            frame_start_ms: None,
+            finished: false,
+        }
+    }
+
+    pub fn mode(self, mode: PlayMode) -> Self {
+        Animation { mode, ..self }
+    }
+
+    /// Count of frames.
+    pub fn len(&self) -> usize {
+        match self.frames {
+            Frames::Slice(frames) => frames.len(),
+            Frames::SpriteSheet(sheet) => sheet.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn frame(&self, idx: usize) -> Option<Frame<'a>> {
+        match self.frames {
+            Frames::Slice(frames) => frames.get(idx).copied(),
+            Frames::SpriteSheet(sheet) => sheet.frame(idx),
+        }
+    }
+
+    /// Index of the current frame.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// `PlayMode::Once` animation has shown its last frame for its duration.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Start playing from the first frame.
+    pub fn reset(&mut self) {
+        self.current = 0;
+        self.forward = true;
+        self.frame_start_ms = None;
+        self.finished = false;
+    }
+
+    /// Frame which has to be shown at `now_ms`, `None` if the shown frame is not changed.
+    /// The first call starts the animation. One call advances at most one frame.
+    pub fn poll(&mut self, now_ms: u32) -> Option<Frame<'a>> {
+        if self.finished || self.is_empty() {
+            return None;
+        }
+        let Some(frame_start_ms) = self.frame_start_ms else {
+            self.frame_start_ms = Some(now_ms);
+            return self.frame(self.current);
+        };
+
+        let duration_ms = self.frame(self.current)?.duration_ms as u32;
+        if now_ms.wrapping_sub(frame_start_ms) < duration_ms {
+            return None;
+        }
+        self.frame_start_ms = Some(frame_start_ms.wrapping_add(duration_ms));
+
+        let previous = self.current;
+        self.advance();
+        if self.current == previous {
+            return None;
+        }
+        self.frame(self.current)
+    }
+
+    fn advance(&mut self) {
+        let last = self.len() - 1;
+        match self.mode {
+            PlayMode::Once if self.current == last => self.finished = true,
+            PlayMode::Once => self.current += 1,
+            PlayMode::Loop => {
+                self.current = if self.current == last {
+                    0
+                } else {
+                    self.current + 1
+                }
+            }
+            PlayMode::PingPong => {
+                if last == 0 {
+                    return;
+                }
+                if self.forward && self.current == last || !self.forward && self.current == 0 {
+                    self.forward = !self.forward;
+                }
+                if self.forward {
+                    self.current += 1;
+                } else {
+                    self.current -= 1;
+                }
+            }
+        }
+    }
+}
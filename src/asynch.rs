@@ -0,0 +1,25 @@
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+
+#[cfg(feature = "font")]
+use crate::font::{self, Font};
+#[cfg(feature = "font")]
+use crate::marquee::Marquee;
+use crate::state::*;
+use crate::{
+    Address, Animation, AudioInputGain, AudioMode, DisplayMode, DriverError, Fade, Geometry,
+    LightingIntensity, MatrixDimensions, Orientation, PixelMap, RegisterShadow, ResyncPolicy,
+    WriteMode, DEFAULT_LIGHTING_INTENSITY, FADE_STEP_MS,
+};
+
+driver!(
+    /// Async driver, the same API as [`IS31FL3728`](crate::IS31FL3728)
+    /// on top of [`embedded_hal_async::i2c::I2c`].
+    IS31FL3728Async,
+    [async],
+    [.await]
+);
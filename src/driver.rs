@@ -0,0 +1,643 @@
+//! Both drivers are generated from one definition: the blocking one calls
+//! `embedded_hal` traits, the async one calls `embedded_hal_async` traits and awaits them.
+
+/// Driver struct and its methods. `I2c` and `DelayNs` are resolved in the module
+/// which calls the macro, `$async` is `async` and `$await` is `.await` for the async driver.
+macro_rules! driver {
+    ($(#[$meta:meta])* $name:ident, [$($async:tt)?], [$($await:tt)*]) => {
+        $(#[$meta])*
+        pub struct $name<I2C, G: Geometry = MatrixDimensions> {
+            i2c: I2C,
+            state: State,
+            geometry: PhantomData<G>,
+        }
+
+        impl<I2C, G: Geometry> $name<I2C, G> {
+            /// Destroy the driver and return the I2C bus.
+            pub fn release(self) -> I2C {
+                self.i2c
+            }
+
+            /// Counts of rows.
+            pub fn rows_count(&self) -> u8 {
+                self.state.rows_count
+            }
+
+            /// Counts of columns.
+            pub fn columns_count(&self) -> u8 {
+                self.state.columns_count
+            }
+
+            /// The biggest built-in font which fits to the height of the matrix:
+            /// 5x7 for 8x8 and 7x9 array modes, 3x5 for others.
+            #[cfg(feature = "font")]
+            pub fn font(&self) -> &'static Font {
+                Font::fitting(self.height()).unwrap_or(&font::FONT_3X5)
+            }
+
+            /// Width of the matrix in pixels according to the orientation.
+            pub fn width(&self) -> u8 {
+                self.state.size().0
+            }
+
+            /// Height of the matrix in pixels according to the orientation.
+            pub fn height(&self) -> u8 {
+                self.state.size().1
+            }
+
+            /// Wiring of LEDs to pins of the chip.
+            pub fn pixel_map(&self) -> PixelMap {
+                self.state.pixel_map
+            }
+
+            /// Registers which are written to the chip.
+            pub fn registers(&self) -> &RegisterShadow {
+                &self.state.registers
+            }
+
+            /// Framebuffer, columns of the chip as they are sent (the pixel map is applied).
+            pub fn frame(&self) -> &[u8] {
+                &self.state.frame[..self.state.columns_count as usize]
+            }
+
+            pub fn intensity(&self) -> LightingIntensity {
+                // the driver writes only valid intensities
+                self.state
+                    .registers
+                    .intensity()
+                    .unwrap_or(DEFAULT_LIGHTING_INTENSITY)
+            }
+
+            pub fn audio_gain(&self) -> AudioInputGain {
+                self.state.registers.audio_gain()
+            }
+
+            pub fn is_shutdown(&self) -> bool {
+                self.state.registers.is_shutdown()
+            }
+
+            /// Audio input, display mode and gain which are set to the chip.
+            pub fn audio_mode(&self) -> AudioMode {
+                self.state.registers.audio_mode()
+            }
+
+            /// How the matrix is mounted.
+            pub fn orientation(&self) -> Orientation {
+                self.state.orientation
+            }
+
+            /// Turn on or off pixel in the framebuffer. Use `flush` to show changes.
+            /// Pixels outside of the matrix are ignored.
+            pub fn set_pixel(&mut self, x: u8, y: u8, on: bool) {
+                self.state.set_pixel(x, y, on)
+            }
+
+            /// State of pixel in the framebuffer. Pixels outside of the matrix are off.
+            pub fn get_pixel(&self, x: u8, y: u8) -> bool {
+                self.state.get_pixel(x, y)
+            }
+
+            /// Invert pixel in the framebuffer. Use `flush` to show changes.
+            pub fn toggle_pixel(&mut self, x: u8, y: u8) {
+                self.state.toggle_pixel(x, y)
+            }
+
+            /// How several columns are sent to the chip.
+            pub fn write_mode(&self) -> WriteMode {
+                self.state.write_mode
+            }
+
+            /// Set how several columns are sent to the chip. Default is [`WriteMode::Burst`],
+            /// use [`WriteMode::PerColumn`] if your bus can't handle long transactions.
+            pub fn set_write_mode(&mut self, write_mode: WriteMode) {
+                self.state.write_mode = write_mode
+            }
+
+            pub fn resync_policy(&self) -> ResyncPolicy {
+                self.state.resync_policy
+            }
+
+            /// Set when the driver rewrites registers of the chip.
+            /// Default is [`ResyncPolicy::Manual`].
+            pub fn set_resync_policy(&mut self, resync_policy: ResyncPolicy) {
+                self.state.resync_policy = resync_policy
+            }
+        }
+
+        impl<I2C, G, E> $name<I2C, G>
+        where
+            I2C: I2c<Error = E>,
+            G: Geometry,
+            E: Debug,
+        {
+            /// Create instance of driver
+            pub $($async)? fn new(
+                i2c: I2C,
+                address: Address,
+                matrix_dimensions: G,
+                audio_input_enabled: bool,
+            ) -> Result<$name<I2C, G>, DriverError<E>> {
+                let mut driver = $name {
+                    i2c,
+                    state: State::new(
+                        address,
+                        matrix_dimensions.dimensions(),
+                        audio_input_enabled,
+                    )?,
+                    geometry: PhantomData,
+                };
+
+                driver.init()$($await)*?;
+
+                Ok(driver)
+            }
+
+            /// Check that the chip answers on the address, then create instance of driver.
+            pub $($async)? fn try_new(
+                mut i2c: I2C,
+                address: Address,
+                matrix_dimensions: G,
+                audio_input_enabled: bool,
+            ) -> Result<$name<I2C, G>, DriverError<E>> {
+                Self::probe(&mut i2c, address)$($await)*?;
+                Self::new(i2c, address, matrix_dimensions, audio_input_enabled)$($await)*
+            }
+
+            /// Check that a device acknowledges the address.
+            ///
+            /// The chip's registers are write-only, so probe sends the address without data.
+            /// Your HAL must support zero-length writes.
+            pub $($async)? fn probe(
+                i2c: &mut I2C,
+                address: Address,
+            ) -> Result<(), DriverError<E>> {
+                let address = State::check_address(address)?;
+                i2c.write(address, &[])$($await)*.map_err(DriverError::I2C)
+            }
+
+            /// Set how the matrix is mounted. All drawing methods take it into account,
+            /// the current picture isn't changed, so redraw it.
+            ///
+            /// Raw column writes (`send_column`, `draw_column`, `write_frame`, `write_columns`)
+            /// ignore it, they address columns of the matrix as it is wired.
+            ///
+            /// Frames and bitmaps of static geometries have the shape of the unrotated matrix,
+            /// so rotation of non-square one by 90 or 270 degrees returns `UnsupportedMode`
+            /// and the orientation isn't changed. Use `MatrixDimensions` for such matrices.
+            ///
+            /// <div class="warning">With `MatrixDimensions` `draw` can't draw rotated by 90
+            /// or 270 degrees non-square matrix, because the rotated column doesn't fit to a byte,
+            /// it returns `UnsupportedMode`. Use `draw_bitmap` or pixels.</div>
+            pub fn set_orientation(
+                &mut self,
+                orientation: Orientation,
+            ) -> Result<(), DriverError<E>> {
+                if !G::supports(orientation) {
+                    return Err(DriverError::UnsupportedMode);
+                }
+                self.state.orientation = orientation;
+                Ok(())
+            }
+
+            $($async)? fn write_i2c(&mut self, write: &[u8]) -> Result<(), DriverError<E>> {
+                if self.state.resync_needed {
+                    self.resync()$($await)*?;
+                }
+                self.write_raw(write)$($await)*
+            }
+
+            /// Send the write and commit it to the shadow, without resync.
+            $($async)? fn write_raw(&mut self, write: &[u8]) -> Result<(), DriverError<E>> {
+                log_write!(self.state.address, write);
+                let result = self
+                    .i2c
+                    .write(self.state.address, write)$($await)*
+                    .map_err(DriverError::I2C);
+                if result.is_err() {
+                    log_write_error!(self.state.address, write.first().copied().unwrap_or(0));
+                }
+                self.state.written(write, &result);
+                result
+            }
+
+            /// Send all columns of the chip according to the write mode, without resync.
+            $($async)? fn write_raw_columns(
+                &mut self,
+                columns: &[u8],
+            ) -> Result<(), DriverError<E>> {
+                match self.state.write_mode {
+                    WriteMode::Burst => {
+                        let (command, len) = State::burst_command(1, columns);
+                        self.write_raw(&command[..len])$($await)*
+                    }
+                    WriteMode::PerColumn => {
+                        for (column_idx, column) in columns.iter().enumerate() {
+                            self.write_raw(&[column_idx as u8 + 1, *column])$($await)*?;
+                        }
+                        Ok(())
+                    }
+                }
+            }
+
+            /// Reset all registers of the chip to defaults by the reset register and configure
+            /// the array mode again. The audio input is disabled, the intensity is 40mA,
+            /// the framebuffer is cleared.
+            pub $($async)? fn reset(&mut self) -> Result<(), DriverError<E>> {
+                log_event!(self.state.address, "reset");
+                let dimensions = self.state.registers.dimensions();
+                self.write_raw(&[RESET_ADDRESS, 0])$($await)*?;
+                self.state.reset(dimensions);
+                self.init()$($await)*
+            }
+
+            /// Rewrite all registers of the chip from the shadow: configuration, lighting effect,
+            /// audio EQ, the shown columns and the columns which are sent, but not updated yet.
+            ///
+            /// Use it when the chip could lose its registers, for example after a brown-out.
+            pub $($async)? fn resync(&mut self) -> Result<(), DriverError<E>> {
+                let registers = self.state.registers.clone();
+                let columns_count = self.state.columns_count as usize;
+                log_event!(self.state.address, "resync");
+                self.write_raw(&[CONFIGURATION_ADDRESS, registers.configuration()])$($await)*?;
+                self.write_raw(&[LIGHTING_EFFECT_ADDRESS, registers.lighting_effect()])$($await)*?;
+                self.write_raw(&[AUDIO_EQ_ADDRESS, registers.audio_eq()])$($await)*?;
+                self.write_raw_columns(&registers.latched_columns()[..columns_count])$($await)*?;
+                self.write_raw(&[UPDATE_COLUMN_ADDRESS, 0])$($await)*?;
+                self.write_raw_columns(&registers.pending_columns()[..columns_count])$($await)*?;
+                self.state.resync_needed = false;
+                Ok(())
+            }
+
+            $($async)? fn write_config(&mut self, configuration: u8) -> Result<(), DriverError<E>> {
+                self.write_i2c(&[CONFIGURATION_ADDRESS, configuration])$($await)*
+            }
+
+            /// Send configuration by I2C if it differs from the persisted one
+            $($async)? fn update_config(
+                &mut self,
+                configuration: u8,
+            ) -> Result<(), DriverError<E>> {
+                if self.state.registers.configuration() != configuration {
+                    self.write_config(configuration)$($await)*?;
+                }
+                Ok(())
+            }
+
+            $($async)? fn write_audio_eq(
+                &mut self,
+                configuration: u8,
+            ) -> Result<(), DriverError<E>> {
+                self.write_i2c(&[AUDIO_EQ_ADDRESS, configuration])$($await)*
+            }
+
+            /// Send configuration by I2C and persist a new configuration to this instance
+            $($async)? fn update_lighting_effect(
+                &mut self,
+                configuration: u8,
+            ) -> Result<(), DriverError<E>> {
+                if self.state.registers.lighting_effect() != configuration {
+                    self.write_i2c(&[LIGHTING_EFFECT_ADDRESS, configuration])$($await)*?;
+                }
+                Ok(())
+            }
+
+            /// Init
+            $($async)? fn init(&mut self) -> Result<(), DriverError<E>> {
+                // the chip may keep the configuration from the previous run, so always write it
+                let configuration = self.state.registers.configuration();
+                self.write_config(configuration)$($await)*?;
+
+                Ok(())
+            }
+
+            /// Update column data registers from temporary data registers.
+            pub $($async)? fn update(&mut self) -> Result<(), DriverError<E>> {
+                self.write_i2c(&[UPDATE_COLUMN_ADDRESS, 0])$($await)*
+            }
+
+            /// Send data to temporary registers. It's a raw column of the matrix,
+            /// the orientation isn't applied.
+            /// <div class="warning">`column_number` starts from 1.</div>
+            pub $($async)? fn send_column(
+                &mut self,
+                column_number: u8,
+                column: u8,
+            ) -> Result<(), DriverError<E>> {
+                self.state.check_columns(column_number, 1)?;
+                let wire_idx = self.state.set_frame_column(column_number - 1, column);
+                self.state.mark_dirty(wire_idx);
+                self.write_wire(wire_idx..wire_idx + 1)$($await)*
+            }
+
+            /// Send columns of the chip (zero-based) from the framebuffer according
+            /// to the write mode.
+            $($async)? fn write_wire(&mut self, range: Range<u8>) -> Result<(), DriverError<E>> {
+                let frame = self.state.frame;
+                match self.state.write_mode {
+                    WriteMode::Burst => {
+                        let first_column_number = range.start + 1;
+                        let (command, len) = State::burst_command(
+                            first_column_number,
+                            &frame[range.start as usize..range.end as usize],
+                        );
+                        self.write_i2c(&command[..len])$($await)*?;
+                        self.state.wire_sent(range);
+                    }
+                    WriteMode::PerColumn => {
+                        for wire_idx in range {
+                            // columns inside the range may be unchanged, send only dirty ones
+                            if !self.state.is_dirty(wire_idx) {
+                                continue;
+                            }
+                            let column_number = wire_idx + 1;
+                            let column = frame[wire_idx as usize];
+                            self.write_i2c(&[column_number, column])$($await)*?;
+                            self.state.wire_sent(wire_idx..wire_idx + 1);
+                        }
+                    }
+                }
+                Ok(())
+            }
+
+            /// Send data to temporary register and update columns registers. It's a raw column
+            /// of the matrix, the orientation isn't applied.
+            /// <div class="warning">`column_number` starts from 1.</div>
+            pub $($async)? fn draw_column(
+                &mut self,
+                column_number: u8,
+                column: u8,
+            ) -> Result<(), DriverError<E>> {
+                self.send_column(column_number, column)$($await)*?;
+                self.update()$($await)*
+            }
+
+            /// Copy columns to the framebuffer and flush it.
+            /// Picture is array of columns, its type depends on the [`Geometry`].
+            pub $($async)? fn draw(&mut self, picture: &G::Frame) -> Result<(), DriverError<E>> {
+                self.state.set_frame(picture.as_ref())?;
+                self.flush()$($await)*
+            }
+
+            /// Send columns which were changed since the last flush and update columns registers.
+            pub $($async)? fn flush(&mut self) -> Result<(), DriverError<E>> {
+                self.send_changes()$($await)*?;
+                self.update()$($await)*
+            }
+
+            /// Send columns which were changed since the last flush to temporary registers.
+            ///
+            /// Use `update` to show them, for example to update several chips back to back.
+            pub $($async)? fn send_changes(&mut self) -> Result<(), DriverError<E>> {
+                if let Some(range) = self.state.dirty_range() {
+                    self.write_wire(range)$($await)*?;
+                }
+                Ok(())
+            }
+
+            /// Send columns to temporary registers starting from the first column.
+            /// Picture is array of raw columns of the matrix, the orientation isn't applied,
+            /// use `draw` for the oriented picture. Its type depends on the [`Geometry`].
+            ///
+            /// Use `update` to show them.
+            pub $($async)? fn write_frame(
+                &mut self,
+                picture: &G::Frame,
+            ) -> Result<(), DriverError<E>> {
+                self.write_columns(1, picture.as_ref())$($await)*
+            }
+
+            /// Send consecutive raw columns of the matrix to temporary registers according
+            /// to the write mode, the orientation isn't applied.
+            /// <div class="warning">`first_column_number` starts from 1.</div>
+            pub $($async)? fn write_columns(
+                &mut self,
+                first_column_number: u8,
+                columns: &[u8],
+            ) -> Result<(), DriverError<E>> {
+                if columns.is_empty() {
+                    return Ok(());
+                }
+                self.state
+                    .check_columns(first_column_number, columns.len())?;
+                let range = self
+                    .state
+                    .set_frame_columns(first_column_number - 1, columns);
+                self.write_wire(range)$($await)*
+            }
+
+            /// Set wiring of LEDs to pins of the chip. The framebuffer is cleared,
+            /// so redraw the picture.
+            pub fn set_pixel_map(&mut self, pixel_map: PixelMap) -> Result<(), DriverError<E>> {
+                self.state.set_pixel_map(pixel_map)
+            }
+
+            /// Set intensity of led's matrix.
+            pub $($async)? fn set_intensity(
+                &mut self,
+                intensity: LightingIntensity,
+            ) -> Result<(), DriverError<E>> {
+                let configuration = self.state.intensity_configuration(intensity);
+                self.update_lighting_effect(configuration)$($await)*
+            }
+
+            /// Set audio input gain
+            pub $($async)? fn set_audio_input_gain(
+                &mut self,
+                gain: AudioInputGain,
+            ) -> Result<(), DriverError<E>> {
+                let configuration = self.state.audio_input_gain_configuration(gain);
+                self.update_lighting_effect(configuration)$($await)*
+            }
+
+            /// Set audio input, display mode and gain, only changed registers are sent.
+            pub $($async)? fn set_audio_mode(
+                &mut self,
+                audio_mode: AudioMode,
+            ) -> Result<(), DriverError<E>> {
+                self.set_audio_input(audio_mode.input_enabled)$($await)*?;
+                self.set_display_mode(audio_mode.display_mode)$($await)*?;
+                self.set_audio_input_gain(audio_mode.gain)$($await)*
+            }
+
+            /// Enable or disable modulation of the picture by the audio input.
+            pub $($async)? fn set_audio_input(
+                &mut self,
+                enabled: bool,
+            ) -> Result<(), DriverError<E>> {
+                let configuration = self.state.audio_input_configuration(enabled);
+                self.update_config(configuration)$($await)*
+            }
+
+            /// Switch between the matrix display mode and the audio EQ bars.
+            pub $($async)? fn set_display_mode(
+                &mut self,
+                display_mode: DisplayMode,
+            ) -> Result<(), DriverError<E>> {
+                let configuration = State::audio_eq_configuration(display_mode);
+                if self.state.registers.audio_eq() != configuration {
+                    self.write_audio_eq(configuration)$($await)*?;
+                }
+                Ok(())
+            }
+
+            /// Enable audio equalize
+            pub $($async)? fn audio_eq_enable(&mut self) -> Result<(), DriverError<E>> {
+                self.write_audio_eq(AUDIO_EQ_ENABLED)$($await)*
+            }
+
+            /// Disable audio equalize
+            pub $($async)? fn audio_eq_disable(&mut self) -> Result<(), DriverError<E>> {
+                self.write_audio_eq(AUDIO_EQ_DISABLED)$($await)*
+            }
+
+            /// Copy bitmap to the framebuffer and flush it.
+            /// Picture is array of rows, `u8` for 8 columns and `u16` for more columns.
+            /// Size of the bitmap must match the size of the matrix according to the orientation.
+            /// The oldest used bit of a row is the leftmost pixel.
+            ///
+            /// Use this method to simplify a work with led-matrix-editors like this one:
+            /// <https://xantorohara.github.io/led-matrix-editor/>
+            pub $($async)? fn draw_bitmap(
+                &mut self,
+                picture: &G::Bitmap,
+            ) -> Result<(), DriverError<E>> {
+                self.state.set_bitmap(picture)?;
+                self.flush()$($await)*
+            }
+
+            /// Show the character with the top left corner at `x` and `y`.
+            #[cfg(feature = "font")]
+            pub $($async)? fn draw_char(
+                &mut self,
+                x: u8,
+                y: u8,
+                c: char,
+                font: &Font,
+            ) -> Result<(), DriverError<E>> {
+                let mut buf = [0; 4];
+                self.draw_text(x, y, c.encode_utf8(&mut buf), font)$($await)*
+            }
+
+            /// Show the text with the top left corner at `x` and `y`, glyphs are separated
+            /// by one column. The text outside of the matrix is cut, use `font` to get a font
+            /// which fits to the matrix.
+            #[cfg(feature = "font")]
+            pub $($async)? fn draw_text(
+                &mut self,
+                x: u8,
+                y: u8,
+                text: &str,
+                font: &Font,
+            ) -> Result<(), DriverError<E>> {
+                self.state.set_text(x, y, text, font);
+                self.flush()$($await)*
+            }
+
+            /// Move the marquee by one column and show it.
+            /// Returns `false` if scrolling is finished.
+            #[cfg(feature = "font")]
+            pub $($async)? fn marquee_step<const N: usize>(
+                &mut self,
+                marquee: &mut Marquee<N>,
+            ) -> Result<bool, DriverError<E>> {
+                if !marquee.advance(self.width() as u16) {
+                    return Ok(false);
+                }
+                self.state.set_marquee(marquee);
+                self.flush()$($await)*?;
+                Ok(true)
+            }
+
+            /// Make a step of the marquee if its step period is passed at `now_ms`.
+            /// Returns `false` if scrolling is finished.
+            #[cfg(feature = "font")]
+            pub $($async)? fn marquee_tick<const N: usize>(
+                &mut self,
+                marquee: &mut Marquee<N>,
+                now_ms: u32,
+            ) -> Result<bool, DriverError<E>> {
+                if marquee.is_due(now_ms) {
+                    self.marquee_step(marquee)$($await)*?;
+                }
+                Ok(!marquee.is_finished(self.width() as u16))
+            }
+
+            /// Set all led's to off. If you want just turn off matrix without
+            /// changing picture, use `software_shutdown`
+            pub $($async)? fn clear(&mut self) -> Result<(), DriverError<E>> {
+                self.state.fill_frame(0);
+                self.flush()$($await)*
+            }
+
+            /// Set all led's to on
+            pub $($async)? fn fill(&mut self) -> Result<(), DriverError<E>> {
+                // if rows count less then 8, older bit will be ignored
+                self.state.fill_frame(0b1111_1111);
+                self.flush()$($await)*
+            }
+
+            /// Turn off matrix output with saving all registry.
+            /// Use `software_on` to return image
+            pub $($async)? fn software_shutdown(&mut self) -> Result<(), DriverError<E>> {
+                let configuration = self.state.shutdown_configuration();
+                self.write_config(configuration)$($await)*
+            }
+
+            /// Turn on matrix output
+            pub $($async)? fn software_on(&mut self) -> Result<(), DriverError<E>> {
+                let configuration = self.state.on_configuration();
+                self.write_config(configuration)$($await)*
+            }
+
+            /// Show the frame of the animation for the time `now_ms` if it is changed,
+            /// the first call starts the animation. Returns `false` when the animation is finished.
+            pub $($async)? fn animation_tick(
+                &mut self,
+                animation: &mut Animation<'_>,
+                now_ms: u32,
+            ) -> Result<bool, DriverError<E>> {
+                if let Some(frame) = animation.poll(now_ms) {
+                    if let Some(intensity) = frame.intensity {
+                        self.set_intensity(intensity)$($await)*?;
+                    }
+                    self.state.set_frame(frame.columns)?;
+                    self.flush()$($await)*?;
+                }
+                Ok(!animation.is_finished())
+            }
+
+            /// Apply the fade at the time `now_ms`, the first call starts it.
+            /// Returns `false` when the fade is finished.
+            pub $($async)? fn fade_tick(
+                &mut self,
+                fade: &mut Fade,
+                now_ms: u32,
+            ) -> Result<bool, DriverError<E>> {
+                let step = fade.step(now_ms);
+                if let Some(intensity) = step.intensity {
+                    self.set_intensity(intensity)$($await)*?;
+                }
+                match step.power {
+                    Some(true) => self.software_on()$($await)*?,
+                    Some(false) => self.software_shutdown()$($await)*?,
+                    None => {}
+                }
+                Ok(!step.finished)
+            }
+
+            /// Run not started fade to the end, waiting `FADE_STEP_MS` between steps.
+            pub $($async)? fn fade(
+                &mut self,
+                mut fade: Fade,
+                delay: &mut impl DelayNs,
+            ) -> Result<(), DriverError<E>> {
+                let mut now_ms = 0;
+                while self.fade_tick(&mut fade, now_ms)$($await)*? {
+                    delay.delay_ms(FADE_STEP_MS)$($await)*;
+                    now_ms += FADE_STEP_MS;
+                }
+                Ok(())
+            }
+        }
+    };
+}
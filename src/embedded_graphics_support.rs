@@ -3,18 +3,19 @@ use core::fmt::Debug;
 use embedded_graphics_core::prelude::*;
 use embedded_graphics_core::{draw_target::DrawTarget, pixelcolor, prelude::OriginDimensions};
 
-use crate::{DriverError, IS31FL3728};
+use crate::{DriverError, Geometry, GrayscaleDisplay, TiledDisplay, IS31FL3728};
 
-impl<E> OriginDimensions for IS31FL3728<E> {
+impl<I2C, G: Geometry> OriginDimensions for IS31FL3728<I2C, G> {
     fn size(&self) -> Size {
-        return Size::new(self.columns_count.into(), self.rows_count.into());
+        Size::new(self.width().into(), self.height().into())
     }
 }
 
-impl<I2C, E> DrawTarget for IS31FL3728<I2C> 
+impl<I2C, G, E> DrawTarget for IS31FL3728<I2C, G>
 where
     I2C: embedded_hal::i2c::I2c<Error = E>,
-    E: Debug
+    G: Geometry,
+    E: Debug,
 {
     type Color = pixelcolor::BinaryColor;
 
@@ -24,26 +25,92 @@ where
     where
         I: IntoIterator<Item = embedded_graphics_core::Pixel<Self::Color>>,
     {
-        let max_x: i32 = (self.columns_count - 1).into();
-        let max_y: i32 = (self.rows_count - 1).into();
-
-        let mut buffer: [u8; crate::MAX_COLUMNS] = [0; crate::MAX_COLUMNS];
+        let max_x: i32 = (self.width() - 1).into();
+        let max_y: i32 = (self.height() - 1).into();
 
         for Pixel(coord, color) in pixels.into_iter() {
-            // the buffer filled by 0 (each pixel is "off"), so process only "on" pixels.
-            if color.is_on() {
-                let (x, y) = (coord.x, coord.y);
-                if (x >= 0 && x <= max_x) && (y >= 0 && y <= max_y) {
-                    let column_idx = x as usize;
-                    buffer[column_idx] = buffer[column_idx] | (0b1000_0000 >> y);
-                };
-            }
+            let (x, y) = (coord.x, coord.y);
+            if (x >= 0 && x <= max_x) && (y >= 0 && y <= max_y) {
+                self.set_pixel(x as u8, y as u8, color.is_on());
+            };
         }
 
-        for idx in 0..self.columns_count {
-            self.send_column(idx+1, buffer[idx as usize])?
+        self.flush()
+    }
+}
+
+impl<I2C, const N: usize, G: Geometry> OriginDimensions for TiledDisplay<I2C, N, G> {
+    fn size(&self) -> Size {
+        Size::new(self.width().into(), self.height().into())
+    }
+}
+
+impl<I2C, E, const N: usize, G> DrawTarget for TiledDisplay<I2C, N, G>
+where
+    I2C: embedded_hal::i2c::I2c<Error = E>,
+    G: Geometry,
+    E: Debug,
+{
+    type Color = pixelcolor::BinaryColor;
+
+    type Error = DriverError<E>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), DriverError<E>>
+    where
+        I: IntoIterator<Item = embedded_graphics_core::Pixel<Self::Color>>,
+    {
+        let max_x: i32 = i32::from(self.width()) - 1;
+        let max_y: i32 = i32::from(self.height()) - 1;
+
+        for Pixel(coord, color) in pixels.into_iter() {
+            let (x, y) = (coord.x, coord.y);
+            if (x >= 0 && x <= max_x) && (y >= 0 && y <= max_y) {
+                self.set_pixel(x as u16, y as u16, color.is_on());
+            };
         }
 
-        self.update()
+        self.flush()
     }
 }
+
+impl<I2C, G: Geometry, const BITS: u8> OriginDimensions for GrayscaleDisplay<I2C, G, BITS> {
+    fn size(&self) -> Size {
+        Size::new(self.width().into(), self.height().into())
+    }
+}
+
+macro_rules! grayscale_draw_target {
+    ($bits:literal, $color:ty) => {
+        /// Drawing changes levels of pixels, they are shown by `tick`.
+        impl<I2C, G, E> DrawTarget for GrayscaleDisplay<I2C, G, $bits>
+        where
+            I2C: embedded_hal::i2c::I2c<Error = E>,
+            G: Geometry,
+            E: Debug,
+        {
+            type Color = $color;
+
+            type Error = DriverError<E>;
+
+            fn draw_iter<I>(&mut self, pixels: I) -> Result<(), DriverError<E>>
+            where
+                I: IntoIterator<Item = embedded_graphics_core::Pixel<Self::Color>>,
+            {
+                let max_x: i32 = (self.width() - 1).into();
+                let max_y: i32 = (self.height() - 1).into();
+
+                for Pixel(coord, color) in pixels.into_iter() {
+                    let (x, y) = (coord.x, coord.y);
+                    if (x >= 0 && x <= max_x) && (y >= 0 && y <= max_y) {
+                        self.set_level(x as u8, y as u8, color.luma());
+                    };
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+grayscale_draw_target!(2, pixelcolor::Gray2);
+grayscale_draw_target!(4, pixelcolor::Gray4);
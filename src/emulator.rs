@@ -0,0 +1,268 @@
+//! Register-level emulator of the chip for tests on the host.
+//!
+//! [`Emulator`] implements [`embedded_hal::i2c::I2c`] (and the async trait with `async` feature),
+//! so it is passed to the driver instead of the real bus. Clones share the same chip,
+//! keep one clone to look at the registers and the visible pixels:
+//! ```
+//! use is31fl3728_rs::emulator::Emulator;
+//! use is31fl3728_rs::{Address, MatrixDimensions, IS31FL3728};
+//!
+//! let emulator = Emulator::new(0x60);
+//! let mut led_matrix =
+//!     IS31FL3728::new(emulator.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+//! led_matrix.set_pixel(0, 0, true);
+//! led_matrix.flush().unwrap();
+//! assert!(emulator.pixel(0, 0));
+//! ```
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::vec::Vec;
+
+use embedded_hal::i2c::{self, ErrorKind, ErrorType, NoAcknowledgeSource, Operation};
+
+use crate::state::{
+    AUDIO_EQ_ADDRESS, CONFIGURATION_ADDRESS, LIGHTING_EFFECT_ADDRESS, RESET_ADDRESS,
+    UPDATE_COLUMN_ADDRESS,
+};
+use crate::{
+    AudioInputGain, LightingIntensity, MatrixDimensions, DEFAULT_AUDIO_INPUT_GAIN, MAX_COLUMNS,
+};
+
+// the emulator decodes registers by itself, so it checks the driver instead of repeating it
+const SHUTDOWN_BIT: u8 = 0b1000_0000;
+const AUDIO_INPUT_BIT: u8 = 0b0000_0100;
+const AUDIO_EQ_BIT: u8 = 0b0100_0000;
+const INTENSITY_BITS: u8 = 0b0000_1111;
+const AUDIO_INPUT_GAIN_BITS: u8 = 0b0111_0000;
+
+/// Errors of the emulated bus.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EmulatorError {
+    /// Nobody answered on the address.
+    NoAcknowledge(u8),
+    /// The chip has no registers for reading.
+    Read,
+}
+
+impl i2c::Error for EmulatorError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            EmulatorError::NoAcknowledge(_) => {
+                ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)
+            }
+            EmulatorError::Read => ErrorKind::Other,
+        }
+    }
+}
+
+/// Registers of the chip.
+#[derive(Clone, Debug, Default)]
+struct Chip {
+    configuration: u8,
+    /// Column registers which are written by the bus.
+    temporary: [u8; MAX_COLUMNS],
+    /// Column registers which are shown, `UPDATE_COLUMN_ADDRESS` copies temporary registers here.
+    displayed: [u8; MAX_COLUMNS],
+    lighting_effect: u8,
+    audio_eq: u8,
+    updates: usize,
+}
+
+impl Chip {
+    fn write(&mut self, register: u8, data: u8) {
+        match register {
+            CONFIGURATION_ADDRESS => self.configuration = data,
+            0x01..=0x0B => self.temporary[register as usize - 1] = data,
+            UPDATE_COLUMN_ADDRESS => {
+                self.displayed = self.temporary;
+                self.updates += 1;
+            }
+            LIGHTING_EFFECT_ADDRESS => self.lighting_effect = data,
+            AUDIO_EQ_ADDRESS => self.audio_eq = data,
+            // all registers are set to defaults, the count of updates is kept
+            RESET_ADDRESS => {
+                *self = Chip {
+                    updates: self.updates,
+                    ..Chip::default()
+                }
+            }
+            // other addresses are not used by the chip
+            _ => {}
+        }
+    }
+}
+
+/// Emulated chip on the address, all registers are 0 like after power on.
+#[derive(Clone, Debug)]
+pub struct Emulator {
+    address: u8,
+    chip: Rc<RefCell<Chip>>,
+}
+
+impl Emulator {
+    pub fn new(address: u8) -> Self {
+        Emulator {
+            address,
+            chip: Rc::new(RefCell::new(Chip::default())),
+        }
+    }
+
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Array mode from the configuration register.
+    pub fn dimensions(&self) -> MatrixDimensions {
+        match self.chip.borrow().configuration & 0b11 {
+            0b00 => MatrixDimensions::M8x8,
+            0b01 => MatrixDimensions::M7x9,
+            0b10 => MatrixDimensions::M6x10,
+            _ => MatrixDimensions::M5x11,
+        }
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.chip.borrow().configuration & SHUTDOWN_BIT != 0
+    }
+
+    pub fn is_audio_input_enabled(&self) -> bool {
+        self.chip.borrow().configuration & AUDIO_INPUT_BIT != 0
+    }
+
+    pub fn is_audio_eq_enabled(&self) -> bool {
+        self.chip.borrow().audio_eq & AUDIO_EQ_BIT != 0
+    }
+
+    /// Intensity from the lighting effect register, `None` for the reserved value.
+    pub fn intensity(&self) -> Option<LightingIntensity> {
+        let bits = self.chip.borrow().lighting_effect & INTENSITY_BITS;
+        LightingIntensity::ALL
+            .into_iter()
+            .find(|intensity| *intensity as u8 == bits)
+    }
+
+    /// Gain from the lighting effect register.
+    pub fn audio_input_gain(&self) -> AudioInputGain {
+        let bits = self.chip.borrow().lighting_effect & AUDIO_INPUT_GAIN_BITS;
+        // every value of 3 bits is a gain
+        AudioInputGain::ALL
+            .into_iter()
+            .find(|gain| *gain as u8 == bits)
+            .unwrap_or(DEFAULT_AUDIO_INPUT_GAIN)
+    }
+
+    /// Raw value of the register, `UPDATE_COLUMN_ADDRESS` and unused addresses are 0.
+    pub fn register(&self, address: u8) -> u8 {
+        let chip = self.chip.borrow();
+        match address {
+            CONFIGURATION_ADDRESS => chip.configuration,
+            0x01..=0x0B => chip.temporary[address as usize - 1],
+            LIGHTING_EFFECT_ADDRESS => chip.lighting_effect,
+            AUDIO_EQ_ADDRESS => chip.audio_eq,
+            _ => 0,
+        }
+    }
+
+    /// Column registers which are written, but not updated yet.
+    pub fn temporary_columns(&self) -> [u8; MAX_COLUMNS] {
+        self.chip.borrow().temporary
+    }
+
+    /// Column registers which are shown.
+    pub fn displayed_columns(&self) -> [u8; MAX_COLUMNS] {
+        self.chip.borrow().displayed
+    }
+
+    /// Count of writes to `UPDATE_COLUMN_ADDRESS`.
+    pub fn updates(&self) -> usize {
+        self.chip.borrow().updates
+    }
+
+    /// Is the LED on: it is displayed and the chip is not in shutdown.
+    /// LEDs outside of the array mode are off.
+    ///
+    /// In the audio equalizer display mode the chip shows the audio level instead of the columns,
+    /// it isn't emulated, so all LEDs are off.
+    pub fn pixel(&self, column_idx: u8, row_idx: u8) -> bool {
+        let dimensions = self.dimensions();
+        if self.is_shutdown()
+            || self.is_audio_eq_enabled()
+            || column_idx >= dimensions.columns_count()
+        {
+            return false;
+        }
+        let displayed = self.chip.borrow().displayed[column_idx as usize];
+        dimensions
+            .row_mask(row_idx)
+            .is_some_and(|mask| displayed & mask != 0)
+    }
+
+    /// Visible LEDs, `pixels()[row][column]`, the size depends on the array mode.
+    pub fn pixels(&self) -> Vec<Vec<bool>> {
+        let dimensions = self.dimensions();
+        (0..dimensions.rows_count())
+            .map(|row_idx| {
+                (0..dimensions.columns_count())
+                    .map(|column_idx| self.pixel(column_idx, row_idx))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Power cycle: all registers are 0.
+    pub fn reset(&self) {
+        *self.chip.borrow_mut() = Chip::default();
+    }
+
+    fn transaction_impl(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), EmulatorError> {
+        if address != self.address {
+            return Err(EmulatorError::NoAcknowledge(address));
+        }
+        let mut chip = self.chip.borrow_mut();
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    let Some((register, data)) = bytes.split_first() else {
+                        continue;
+                    };
+                    // the chip increments the address after each byte
+                    for (offset, byte) in data.iter().enumerate() {
+                        chip.write(register.wrapping_add(offset as u8), *byte);
+                    }
+                }
+                Operation::Read(_) => return Err(EmulatorError::Read),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ErrorType for Emulator {
+    type Error = EmulatorError;
+}
+
+impl i2c::I2c for Emulator {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.transaction_impl(address, operations)
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for Emulator {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.transaction_impl(address, operations)
+    }
+}
@@ -0,0 +1,160 @@
+//! Smooth change of the lighting intensity.
+//!
+//! [`Fade`] computes the intensity for the time from its start, the driver applies it:
+//! call `fade_tick` with the current time from a timer or main loop, or `fade` to block
+//! with a delay until the end.
+//! ```no_run
+//! # use is31fl3728_rs::{Address, Fade, LightingIntensity, MatrixDimensions, Ramp, IS31FL3728};
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! # let mut led_matrix = IS31FL3728::new(i2c, Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+//! # fn millis() -> u32 { 0 }
+//! let mut fade = Fade::new(LightingIntensity::C05mA, LightingIntensity::C75mA, 500)
+//!     .ramp(Ramp::Gamma)
+//!     .through_shutdown(true);
+//! while led_matrix.fade_tick(&mut fade, millis()).unwrap() {}
+//! ```
+
+use crate::LightingIntensity;
+
+/// Period of `fade` steps.
+pub const FADE_STEP_MS: u32 = 5;
+
+/// How the intensity changes between the start and the end of fade.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Ramp {
+    /// Current changes with the same speed.
+    Linear,
+    /// Perceived brightness changes with the same speed, current changes slower at low intensity.
+    Gamma,
+}
+
+/// Fade between two intensities over the duration.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Fade {
+    from: LightingIntensity,
+    to: LightingIntensity,
+    duration_ms: u32,
+    ramp: Ramp,
+    through_shutdown: bool,
+    start_ms: Option<u32>,
+    current: Option<LightingIntensity>,
+    finished: bool,
+}
+
+/// Changes of the chip for one tick of fade.
+pub(crate) struct FadeStep {
+    pub(crate) intensity: Option<LightingIntensity>,
+    /// `Some(true)` is `software_on`, `Some(false)` is `software_shutdown`.
+    pub(crate) power: Option<bool>,
+    pub(crate) finished: bool,
+}
+
+impl Fade {
+    /// Linear fade which starts at the first tick.
+    pub fn new(from: LightingIntensity, to: LightingIntensity, duration_ms: u32) -> Self {
+        Fade {
+            from,
+            to,
+            duration_ms,
+            ramp: Ramp::Linear,
+            through_shutdown: false,
+            start_ms: None,
+            current: None,
+            finished: false,
+        }
+    }
+
+    pub fn ramp(self, ramp: Ramp) -> Self {
+        Fade { ramp, ..self }
+    }
+
+    /// Turn on the matrix at the start of fade to higher intensity,
+    /// shutdown it at the end of fade to lower intensity.
+    pub fn through_shutdown(self, through_shutdown: bool) -> Self {
+        Fade {
+            through_shutdown,
+            ..self
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Intensity after `elapsed_ms` from the start.
+    pub fn intensity_at(&self, elapsed_ms: u32) -> LightingIntensity {
+        if elapsed_ms >= self.duration_ms {
+            return self.to;
+        }
+        let from = self.from.milliamps() as u32;
+        let to = self.to.milliamps() as u32;
+        let milliamps = match self.ramp {
+            Ramp::Linear => interpolate(from, to, elapsed_ms, self.duration_ms),
+            Ramp::Gamma => {
+                // perceived brightness is close to the square root of current,
+                // it is computed in hundredths to keep precision
+                let brightness = interpolate(
+                    isqrt(from * 10_000),
+                    isqrt(to * 10_000),
+                    elapsed_ms,
+                    self.duration_ms,
+                );
+                (brightness * brightness + 5_000) / 10_000
+            }
+        };
+        LightingIntensity::from_milliamps(milliamps as u8)
+    }
+
+    /// Compute changes of the chip at the time `now_ms`, the first call starts the fade.
+    pub(crate) fn step(&mut self, now_ms: u32) -> FadeStep {
+        if self.finished {
+            return FadeStep {
+                intensity: None,
+                power: None,
+                finished: true,
+            };
+        }
+        let start_ms = *self.start_ms.get_or_insert(now_ms);
+        let elapsed_ms = now_ms.wrapping_sub(start_ms);
+        let intensity = self.intensity_at(elapsed_ms);
+        let fading_out = self.to.milliamps() < self.from.milliamps();
+
+        let mut power = None;
+        if self.through_shutdown && self.current.is_none() && !fading_out {
+            power = Some(true);
+        }
+        self.finished = elapsed_ms >= self.duration_ms;
+        if self.through_shutdown && self.finished && fading_out {
+            power = Some(false);
+        }
+
+        let changed = self.current != Some(intensity);
+        self.current = Some(intensity);
+        FadeStep {
+            intensity: changed.then_some(intensity),
+            power,
+            finished: self.finished,
+        }
+    }
+}
+
+fn interpolate(from: u32, to: u32, elapsed: u32, duration: u32) -> u32 {
+    let from = from as i64;
+    let to = to as i64;
+    let value = from + (to - from) * elapsed as i64 / duration as i64;
+    value as u32
+}
+
+/// Integer square root, rounded down.
+fn isqrt(value: u32) -> u32 {
+    if value < 2 {
+        return value;
+    }
+    let mut root = value;
+    let mut next = root.div_ceil(2);
+    while next < root {
+        root = next;
+        next = (root + value / root) / 2;
+    }
+    root
+}
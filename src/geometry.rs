@@ -0,0 +1,148 @@
+//! Geometry of the matrix known at compile time.
+//!
+//! Pass one of marker types to `new` instead of [`MatrixDimensions`] and the driver accepts
+//! frames as arrays with exactly one byte per column:
+//! ```no_run
+//! # use is31fl3728_rs::{geometry, Address, IS31FL3728};
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! let mut led_matrix = IS31FL3728::new(i2c, Address::Gnd, geometry::M5x11, false).unwrap();
+//! led_matrix.draw(&[0; 11]).unwrap();
+//! ```
+//! A frame of other length doesn't compile:
+//! ```compile_fail
+//! # use is31fl3728_rs::{geometry, Address, IS31FL3728};
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! let mut led_matrix = IS31FL3728::new(i2c, Address::Gnd, geometry::M5x11, false).unwrap();
+//! led_matrix.draw(&[0; 8]).unwrap();
+//! ```
+
+use crate::{MatrixDimensions, Orientation};
+
+/// Geometry of the matrix: array mode of the chip and type of the frame.
+pub trait Geometry: Copy {
+    /// Columns of the picture, one byte per column.
+    type Frame: AsRef<[u8]> + ?Sized;
+    /// Rows of the picture for `draw_bitmap`.
+    type Bitmap: Rows + ?Sized;
+
+    fn dimensions(&self) -> MatrixDimensions;
+
+    /// Can frames and bitmaps describe the matrix mounted with the orientation.
+    fn supports(orientation: Orientation) -> bool {
+        let _ = orientation;
+        true
+    }
+}
+
+/// Geometry which is known at compile time.
+pub trait StaticGeometry: Geometry {
+    const DIMENSIONS: MatrixDimensions;
+    const ROWS: u8;
+    const COLUMNS: u8;
+}
+
+/// Geometry selected at runtime, frame is a slice of any length
+/// and bitmap is checked at runtime.
+impl Geometry for MatrixDimensions {
+    type Frame = [u8];
+    type Bitmap = dyn Rows;
+
+    fn dimensions(&self) -> MatrixDimensions {
+        *self
+    }
+}
+
+/// Rows of a bitmap. The oldest used bit of a row is the leftmost pixel,
+/// so `0b1_0000_0000_0` is the first pixel of a row with 11 columns.
+pub trait Rows {
+    fn rows_count(&self) -> usize;
+
+    fn row(&self, idx: usize) -> u16;
+
+    /// Count of pixels which a row holds.
+    fn row_bits(&self) -> u8 {
+        16
+    }
+}
+
+impl<const N: usize> Rows for [u8; N] {
+    fn rows_count(&self) -> usize {
+        N
+    }
+
+    fn row_bits(&self) -> u8 {
+        8
+    }
+
+    fn row(&self, idx: usize) -> u16 {
+        self[idx].into()
+    }
+}
+
+impl<const N: usize> Rows for [u16; N] {
+    fn rows_count(&self) -> usize {
+        N
+    }
+
+    fn row(&self, idx: usize) -> u16 {
+        self[idx]
+    }
+}
+
+impl Rows for [u8] {
+    fn rows_count(&self) -> usize {
+        self.len()
+    }
+
+    fn row_bits(&self) -> u8 {
+        8
+    }
+
+    fn row(&self, idx: usize) -> u16 {
+        self[idx].into()
+    }
+}
+
+impl Rows for [u16] {
+    fn rows_count(&self) -> usize {
+        self.len()
+    }
+
+    fn row(&self, idx: usize) -> u16 {
+        self[idx]
+    }
+}
+
+macro_rules! static_geometry {
+    ($name:ident, $rows:literal, $columns:literal, $row:ty) => {
+        #[doc = concat!("Matrix with ", $rows, " rows and ", $columns, " columns.")]
+        #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+        pub struct $name;
+
+        impl Geometry for $name {
+            type Frame = [u8; $columns];
+            type Bitmap = [$row; $rows];
+
+            fn dimensions(&self) -> MatrixDimensions {
+                MatrixDimensions::$name
+            }
+
+            /// Rotation by 90 or 270 degrees of non-square matrix changes the shape
+            /// of frames and bitmaps, so it isn't supported.
+            fn supports(orientation: Orientation) -> bool {
+                orientation.size($columns, $rows) == ($columns, $rows)
+            }
+        }
+
+        impl StaticGeometry for $name {
+            const DIMENSIONS: MatrixDimensions = MatrixDimensions::$name;
+            const ROWS: u8 = $rows;
+            const COLUMNS: u8 = $columns;
+        }
+    };
+}
+
+static_geometry!(M8x8, 8, 8, u8);
+static_geometry!(M7x9, 7, 9, u16);
+static_geometry!(M6x10, 6, 10, u16);
+static_geometry!(M5x11, 5, 11, u16);
@@ -0,0 +1,157 @@
+//! Software grayscale with binary-coded modulation.
+//!
+//! The chip shows only "on" and "off" pixels. [`GrayscaleDisplay`] keeps a level of each pixel
+//! and shows bit planes of levels one by one: the plane `k` is shown `2^k` ticks,
+//! so an eye sees the average brightness. Call `tick` from a timer with a steady rate.
+
+use core::fmt::Debug;
+
+use embedded_hal::i2c::I2c;
+
+use crate::{DriverError, Geometry, MatrixDimensions, WriteMode, IS31FL3728, MAX_COLUMNS};
+
+/// Refresh rate which is seen without flicker by most people.
+pub const FLICKER_FREE_REFRESH_RATE_HZ: u32 = 100;
+
+/// Limits of grayscale refresh for the speed of the bus.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GrayscaleTiming {
+    /// The shortest period of `tick`, enough to send one bit plane.
+    pub min_tick_period_us: u32,
+    /// The highest refresh rate of the whole picture with the shortest tick period.
+    pub max_refresh_rate_hz: u32,
+}
+
+impl GrayscaleTiming {
+    /// Is the highest refresh rate enough to avoid flicker.
+    pub fn is_flicker_free(&self) -> bool {
+        self.max_refresh_rate_hz >= FLICKER_FREE_REFRESH_RATE_HZ
+    }
+}
+
+/// Grayscale display with `2^BITS` levels of each pixel, `BITS` is from 1 to 4.
+pub struct GrayscaleDisplay<I2C, G: Geometry = MatrixDimensions, const BITS: u8 = 2> {
+    driver: IS31FL3728<I2C, G>,
+    /// Level of each pixel, `levels[y][x]`.
+    levels: [[u8; MAX_COLUMNS]; MAX_COLUMNS],
+    slot: u8,
+    shown_plane: Option<u8>,
+}
+
+impl<I2C, G: Geometry, const BITS: u8> GrayscaleDisplay<I2C, G, BITS> {
+    /// Count of gray levels, including "off".
+    pub const LEVELS: u8 = 1 << BITS;
+
+    /// Count of ticks to show all bit planes once.
+    const SLOTS: u8 = (1 << BITS) - 1;
+
+    pub fn new(driver: IS31FL3728<I2C, G>) -> Self {
+        const {
+            assert!(
+                BITS >= 1 && BITS <= 4,
+                "grayscale supports from 1 to 4 bits"
+            )
+        };
+        GrayscaleDisplay {
+            driver,
+            levels: [[0; MAX_COLUMNS]; MAX_COLUMNS],
+            slot: 0,
+            shown_plane: None,
+        }
+    }
+
+    /// Destroy the display and return the driver.
+    pub fn release(self) -> IS31FL3728<I2C, G> {
+        self.driver
+    }
+
+    /// Width of the display in pixels.
+    pub fn width(&self) -> u8 {
+        self.driver.width()
+    }
+
+    /// Height of the display in pixels.
+    pub fn height(&self) -> u8 {
+        self.driver.height()
+    }
+
+    /// Set level of the pixel, levels above `LEVELS - 1` are saturated.
+    /// Pixels outside of the matrix are ignored.
+    pub fn set_level(&mut self, x: u8, y: u8, level: u8) {
+        if x < self.width() && y < self.height() {
+            self.levels[y as usize][x as usize] = level.min(Self::LEVELS - 1);
+        }
+    }
+
+    /// Level of the pixel. Pixels outside of the matrix are off.
+    pub fn level(&self, x: u8, y: u8) -> u8 {
+        if x < self.width() && y < self.height() {
+            self.levels[y as usize][x as usize]
+        } else {
+            0
+        }
+    }
+
+    /// Set all pixels to "off".
+    pub fn clear(&mut self) {
+        self.levels = [[0; MAX_COLUMNS]; MAX_COLUMNS];
+    }
+
+    /// Limits of refresh for the bus frequency, for example 400_000 for fast mode.
+    /// `None` for the frequency 0.
+    ///
+    /// The worst case is used: every column of the plane is changed.
+    pub fn timing(&self, bus_frequency_hz: u32) -> Option<GrayscaleTiming> {
+        if bus_frequency_hz == 0 {
+            return None;
+        }
+        let columns = self.driver.columns_count() as u64;
+        // each byte is 8 bits and ACK, a transaction adds START and STOP
+        let (bytes, transactions) = match self.driver.write_mode() {
+            WriteMode::Burst => (columns + 2, 1),
+            WriteMode::PerColumn => (columns * 3, columns),
+        };
+        // `update` is one more transaction with 3 bytes
+        let clocks = (bytes + 3) * 9 + (transactions + 1) * 2;
+        // u64 doesn't overflow even for 1 Hz, periods longer than u32::MAX are saturated
+        let min_tick_period_us = (clocks * 1_000_000).div_ceil(bus_frequency_hz as u64);
+        let frame_period_us = min_tick_period_us * Self::SLOTS as u64;
+
+        Some(GrayscaleTiming {
+            min_tick_period_us: u32::try_from(min_tick_period_us).unwrap_or(u32::MAX),
+            max_refresh_rate_hz: (1_000_000 / frame_period_us) as u32,
+        })
+    }
+
+    /// Bit plane which is shown in the slot: plane `k` takes `2^k` slots.
+    fn plane(slot: u8) -> u8 {
+        (slot + 1).ilog2() as u8
+    }
+}
+
+impl<I2C, G, E, const BITS: u8> GrayscaleDisplay<I2C, G, BITS>
+where
+    I2C: I2c<Error = E>,
+    G: Geometry,
+    E: Debug,
+{
+    /// Show the next slot of bit planes. Call it with a steady rate from a timer,
+    /// see `timing` for the shortest period.
+    pub fn tick(&mut self) -> Result<(), DriverError<E>> {
+        let plane = Self::plane(self.slot);
+        self.slot = (self.slot + 1) % Self::SLOTS;
+
+        if self.shown_plane == Some(plane) && Self::SLOTS > 1 {
+            return Ok(());
+        }
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let on = self.levels[y as usize][x as usize] & (1 << plane) != 0;
+                self.driver.set_pixel(x, y, on);
+            }
+        }
+        self.driver.flush()?;
+        self.shown_plane = Some(plane);
+        Ok(())
+    }
+}
@@ -1,46 +1,204 @@
 #![doc = include_str!("../README.md")]
 #![no_std]
+#![allow(clippy::unusual_byte_groupings)]
 
+#[cfg(feature = "emulator")]
+extern crate std;
+
+// macros are visible in modules which are declared after it
+#[macro_use]
+mod logging;
+#[macro_use]
+mod driver;
+
+pub mod animation;
 #[cfg(feature = "embedded-graphics")]
 mod embedded_graphics_support;
+#[cfg(feature = "emulator")]
+pub mod emulator;
+
+#[cfg(feature = "async")]
+mod asynch;
+pub mod fade;
+#[cfg(feature = "font")]
+pub mod font;
+pub mod geometry;
+pub mod grayscale;
+#[cfg(feature = "font")]
+pub mod marquee;
+mod pixel_map;
+pub mod shadow;
+mod state;
+#[cfg(feature = "emulator")]
+pub mod terminal;
+mod tiled;
 
 use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::{self, ErrorKind, I2c};
+
+#[cfg(feature = "async")]
+pub use asynch::IS31FL3728Async;
+#[cfg(feature = "macros")]
+pub use is31fl3728_macros::matrix;
+
+use state::*;
+
+pub use animation::{Animation, Frame, PlayMode, SpriteSheet};
+pub use fade::{Fade, Ramp, FADE_STEP_MS};
+#[cfg(feature = "font")]
+pub use font::Font;
+pub use geometry::Geometry;
+pub use grayscale::GrayscaleDisplay;
+#[cfg(feature = "font")]
+pub use marquee::Marquee;
+pub use pixel_map::PixelMap;
+pub use shadow::RegisterShadow;
+pub use tiled::{Tile, TiledDisplay};
+
+/// Errors of the driver.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DriverError<E: Debug> {
+    /// Error of the I2C bus, see [`ErrorKind`] from `i2c::Error::kind`.
+    I2C(E),
+    /// Column number and the count of columns, column numbers start from 1.
+    ColumnOutOfRange(u8, u8),
+    /// Length of the frame (or count of rows of the bitmap) and the size of the matrix
+    /// which it must fit.
+    FrameLengthMismatch(usize, usize),
+    /// The operation isn't possible with the current orientation,
+    /// for example `draw` of a rotated non-square matrix.
+    UnsupportedMode,
+    /// Reserved or not 7-bit I2C address.
+    InvalidAddress(u8),
+    /// Rows and columns of the pixel map must be permutations of the chip's pins.
+    InvalidPixelMap,
+    /// Pixels which a row of the bitmap holds and the width of the matrix,
+    /// for example `u8` rows for 11 columns.
+    BitmapRowTooNarrow(u8, u8),
+}
+
+impl<E: Debug> core::fmt::Display for DriverError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::I2C(error) => write!(f, "I2C error: {:?}", error),
+            Self::ColumnOutOfRange(column_number, columns_count) => write!(
+                f,
+                "column {} is out of range 1..={}",
+                column_number, columns_count
+            ),
+            Self::FrameLengthMismatch(length, expected) => write!(
+                f,
+                "frame of length {} doesn't fit the matrix of size {}",
+                length, expected
+            ),
+            Self::UnsupportedMode => write!(f, "operation isn't supported by the orientation"),
+            Self::InvalidAddress(address) => write!(f, "invalid I2C address 0x{:02x}", address),
+            Self::InvalidPixelMap => write!(
+                f,
+                "invalid pixel map, rows and columns must be permutations of the chip's pins"
+            ),
+            Self::BitmapRowTooNarrow(row_bits, width) => write!(
+                f,
+                "bitmap row of {} pixels doesn't hold the matrix of width {}",
+                row_bits, width
+            ),
+        }
+    }
+}
 
-use embedded_hal::i2c::I2c;
+impl<E: Debug> core::error::Error for DriverError<E> {}
 
-#[cfg(feature = "rtt-debug")]
-use rtt_target::debug_rprintln;
+/// `?` on results of the bus, for example when other devices share it.
+impl<E: Debug> From<E> for DriverError<E> {
+    fn from(error: E) -> Self {
+        DriverError::I2C(error)
+    }
+}
 
-//#[derive(Debug)]
-pub enum DriverError<E: Debug> {
-    I2C(E),
-    InvalidColumnNumber(u8, u8),
-    IncorrectMatrixSize,
+/// The driver error can be used as an error of the bus, errors of the driver are `Other`.
+impl<E: i2c::Error> i2c::Error for DriverError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            DriverError::I2C(error) => error.kind(),
+            _ => ErrorKind::Other,
+        }
+    }
 }
 
-impl<E: Debug> DriverError<E> {
-    fn invalid_column(actual: u8, max_column: u8) -> Self {
-        Self::InvalidColumnNumber(actual, max_column)
+/// I2C address of the chip, selected by the AD pin connection.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Address {
+    /// AD connected to GND, 0x60.
+    Gnd,
+    /// AD connected to SCL, 0x61.
+    Scl,
+    /// AD connected to SDA, 0x62.
+    Sda,
+    /// AD connected to VCC, 0x63.
+    Vcc,
+    /// Any other 7-bit address, for example behind an address translator.
+    Custom(u8),
+}
+
+impl Address {
+    /// 7-bit I2C address.
+    pub fn value(&self) -> u8 {
+        match *self {
+            Address::Gnd => 0x60,
+            Address::Scl => 0x61,
+            Address::Sda => 0x62,
+            Address::Vcc => 0x63,
+            Address::Custom(address) => address,
+        }
+    }
+
+    /// Addresses 0x00-0x07 and 0x78-0x7F are reserved by the I2C specification.
+    pub fn is_valid(&self) -> bool {
+        (0x08..=0x77).contains(&self.value())
     }
 }
 
-impl<E: Debug> Debug for DriverError<E> {
+/// The byte isn't one of addresses selected by the AD pin (0x60-0x63).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UnknownAddress(pub u8);
+
+impl core::fmt::Display for UnknownAddress {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        match self {
-            Self::I2C(ref i2c_err) => f.debug_tuple("I2C").field(i2c_err).finish(),
-            Self::InvalidColumnNumber(ref actual, ref max) => {
-                write!(f, "OutOfBounds, actual = {}, max = {}", actual, max)
-            }
-            &Self::IncorrectMatrixSize => write!(
-                f,
-                "Incorrect matrix size. Draw bitmaps works only for 8x8 matrices."
-            ),
+        write!(
+            f,
+            "0x{:02x} isn't an address of the chip, use Address::Custom for it",
+            self.0
+        )
+    }
+}
+
+impl core::error::Error for UnknownAddress {}
+
+/// Only addresses of the AD pin are converted, so a typo in the address doesn't pass silently.
+/// Other addresses are set on purpose by `Address::Custom`.
+impl TryFrom<u8> for Address {
+    type Error = UnknownAddress;
+
+    fn try_from(address: u8) -> Result<Self, Self::Error> {
+        match address {
+            0x60 => Ok(Address::Gnd),
+            0x61 => Ok(Address::Scl),
+            0x62 => Ok(Address::Sda),
+            0x63 => Ok(Address::Vcc),
+            address => Err(UnknownAddress(address)),
         }
     }
 }
 
 /// Enumeration of all supported sizes of matrices.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum MatrixDimensions {
     M8x8 = 0b00,
@@ -49,8 +207,47 @@ pub enum MatrixDimensions {
     M5x11 = 0b11,
 }
 
+impl MatrixDimensions {
+    /// Count of rows of the array mode.
+    pub const fn rows_count(&self) -> u8 {
+        match self {
+            MatrixDimensions::M8x8 => 8,
+            MatrixDimensions::M7x9 => 7,
+            MatrixDimensions::M6x10 => 6,
+            MatrixDimensions::M5x11 => 5,
+        }
+    }
+
+    /// Count of columns of the array mode.
+    pub const fn columns_count(&self) -> u8 {
+        match self {
+            MatrixDimensions::M8x8 => 8,
+            MatrixDimensions::M7x9 => 9,
+            MatrixDimensions::M6x10 => 10,
+            MatrixDimensions::M5x11 => 11,
+        }
+    }
+
+    /// Bit of a column register which is the row (zero-based): the top row is the oldest used bit.
+    /// The chip ignores older bits when the mode has less than 8 rows, for example
+    /// the row 0 is `0b0001_0000` in 5x11 mode. `None` if the matrix doesn't have the row.
+    pub const fn row_mask(&self, row_idx: u8) -> Option<u8> {
+        if row_idx >= self.rows_count() {
+            return None;
+        }
+        Some(row_mask(self.rows_count(), row_idx))
+    }
+}
+
+/// Bit of a column with `rows_count` rows which is the row (zero-based),
+/// the row must be less than `rows_count`.
+pub(crate) const fn row_mask(rows_count: u8, row_idx: u8) -> u8 {
+    1 << (rows_count - 1 - row_idx)
+}
+
 /// All supported lighting intensity.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum LightingIntensity {
     C05mA = 0b1000,
@@ -72,309 +269,266 @@ pub enum LightingIntensity {
 
 impl LightingIntensity {
     pub fn next(&self) -> LightingIntensity {
-        match self {
-            &LightingIntensity::C05mA => LightingIntensity::C10mA,
-            &LightingIntensity::C10mA => LightingIntensity::C15mA,
-            &LightingIntensity::C15mA => LightingIntensity::C20mA,
-            &LightingIntensity::C20mA => LightingIntensity::C25mA,
-            &LightingIntensity::C25mA => LightingIntensity::C30mA,
-            &LightingIntensity::C30mA => LightingIntensity::C35mA,
-            &LightingIntensity::C35mA => LightingIntensity::C40mA,
-            &LightingIntensity::C40mA => LightingIntensity::C45mA,
-            &LightingIntensity::C45mA => LightingIntensity::C50mA,
-            &LightingIntensity::C50mA => LightingIntensity::C55mA,
-            &LightingIntensity::C55mA => LightingIntensity::C60mA,
-            &LightingIntensity::C60mA => LightingIntensity::C65mA,
-            &LightingIntensity::C65mA => LightingIntensity::C70mA,
-            &LightingIntensity::C70mA => LightingIntensity::C75mA,
-            &LightingIntensity::C75mA => LightingIntensity::C05mA,
+        match *self {
+            LightingIntensity::C05mA => LightingIntensity::C10mA,
+            LightingIntensity::C10mA => LightingIntensity::C15mA,
+            LightingIntensity::C15mA => LightingIntensity::C20mA,
+            LightingIntensity::C20mA => LightingIntensity::C25mA,
+            LightingIntensity::C25mA => LightingIntensity::C30mA,
+            LightingIntensity::C30mA => LightingIntensity::C35mA,
+            LightingIntensity::C35mA => LightingIntensity::C40mA,
+            LightingIntensity::C40mA => LightingIntensity::C45mA,
+            LightingIntensity::C45mA => LightingIntensity::C50mA,
+            LightingIntensity::C50mA => LightingIntensity::C55mA,
+            LightingIntensity::C55mA => LightingIntensity::C60mA,
+            LightingIntensity::C60mA => LightingIntensity::C65mA,
+            LightingIntensity::C65mA => LightingIntensity::C70mA,
+            LightingIntensity::C70mA => LightingIntensity::C75mA,
+            LightingIntensity::C75mA => LightingIntensity::C05mA,
         }
     }
 
     pub fn prev(&self) -> LightingIntensity {
-        match self {
-            &LightingIntensity::C75mA => LightingIntensity::C70mA,
-            &LightingIntensity::C70mA => LightingIntensity::C65mA,
-            &LightingIntensity::C65mA => LightingIntensity::C60mA,
-            &LightingIntensity::C60mA => LightingIntensity::C55mA,
-            &LightingIntensity::C55mA => LightingIntensity::C50mA,
-            &LightingIntensity::C50mA => LightingIntensity::C45mA,
-            &LightingIntensity::C45mA => LightingIntensity::C40mA,
-            &LightingIntensity::C40mA => LightingIntensity::C35mA,
-            &LightingIntensity::C35mA => LightingIntensity::C30mA,
-            &LightingIntensity::C30mA => LightingIntensity::C25mA,
-            &LightingIntensity::C25mA => LightingIntensity::C20mA,
-            &LightingIntensity::C20mA => LightingIntensity::C15mA,
-            &LightingIntensity::C15mA => LightingIntensity::C10mA,
-            &LightingIntensity::C10mA => LightingIntensity::C05mA,
-            &LightingIntensity::C05mA => LightingIntensity::C75mA,
+        match *self {
+            LightingIntensity::C75mA => LightingIntensity::C70mA,
+            LightingIntensity::C70mA => LightingIntensity::C65mA,
+            LightingIntensity::C65mA => LightingIntensity::C60mA,
+            LightingIntensity::C60mA => LightingIntensity::C55mA,
+            LightingIntensity::C55mA => LightingIntensity::C50mA,
+            LightingIntensity::C50mA => LightingIntensity::C45mA,
+            LightingIntensity::C45mA => LightingIntensity::C40mA,
+            LightingIntensity::C40mA => LightingIntensity::C35mA,
+            LightingIntensity::C35mA => LightingIntensity::C30mA,
+            LightingIntensity::C30mA => LightingIntensity::C25mA,
+            LightingIntensity::C25mA => LightingIntensity::C20mA,
+            LightingIntensity::C20mA => LightingIntensity::C15mA,
+            LightingIntensity::C15mA => LightingIntensity::C10mA,
+            LightingIntensity::C10mA => LightingIntensity::C05mA,
+            LightingIntensity::C05mA => LightingIntensity::C75mA,
         }
     }
-}
 
-/// All supported Audio input gains
-#[derive(Clone, Copy)]
-#[repr(u8)]
-pub enum AudioInputGain {
-    G00dB = 0b0_000_0000,
-    G03dB = 0b0_001_0000,
-    G06dB = 0b0_010_0000,
-    G09dB = 0b0_011_0000,
-    G12dB = 0b0_100_0000,
-    G15dB = 0b0_101_0000,
-    G18dB = 0b0_110_0000,
-    GMinus6dB = 0b111,
-}
-
-/// Driver
-pub struct IS31FL3728<I2C> {
-    i2c: I2C,
-    address: u8,
-    dimensions: MatrixDimensions,
-    audio_input_enabled: bool,
-    rows_count: u8,
-    columns_count: u8,
-    configuration_register: u8,
-    lighting_effects_register: u8,
-}
-
-pub const MAX_COLUMNS: usize = 11;
-
-const CONFIGURATION_ADDRESS: u8 = 0x00;
-const UPDATE_COLUMN_ADDRESS: u8 = 0x0C;
-const LIGHTING_EFFECT_ADDRESS: u8 = 0x0D;
-const AUDIO_EQ_ADDRESS: u8 = 0x0F;
-
-pub const DEFAULT_LIGHTING_INTENSITY: LightingIntensity = LightingIntensity::C40mA;
-pub const DEFAULT_AUDIO_INPUT_GAIN: AudioInputGain = AudioInputGain::G00dB;
-
-const DEFAULT_CONFIGURATION_REGISTER: u8 = 0;
-const DEFAULT_LIGHTING_EFFECT_REGISTER: u8 =
-    (DEFAULT_AUDIO_INPUT_GAIN as u8) | (DEFAULT_LIGHTING_INTENSITY as u8);
-
-impl<I2C, E> IS31FL3728<I2C>
-where
-    I2C: I2c<Error = E>,
-    E: Debug,
-{
-    /// Create instance of driver
-    pub fn new(
-        i2c: I2C,
-        address: u8,
-        matrix_dimensions: MatrixDimensions,
-        audio_input_enabled: bool,
-    ) -> Result<IS31FL3728<I2C>, DriverError<E>> {
-        let (rows_count, columns_count) = match matrix_dimensions {
-            MatrixDimensions::M8x8 => (8, 8),
-            MatrixDimensions::M7x9 => (7, 9),
-            MatrixDimensions::M6x10 => (6, 10),
-            MatrixDimensions::M5x11 => (5, 11),
-        };
-
-        let mut driver = IS31FL3728 {
-            i2c,
-            address,
-            dimensions: matrix_dimensions,
-            audio_input_enabled,
-            rows_count,
-            columns_count,
-            configuration_register: DEFAULT_CONFIGURATION_REGISTER,
-            lighting_effects_register: DEFAULT_LIGHTING_EFFECT_REGISTER,
-        };
-
-        driver.init().unwrap();
-
-        Ok(driver)
-    }
-
-    fn debug(&self, msg: &str, data: u8) {
-        #[cfg(feature = "rtt-debug")]
-        debug_rprintln!("IS31FL3728[0x{:02x}]: {} = {:08b}", self.address, msg, data)
+    /// All intensities from the lowest to the highest.
+    pub const ALL: [LightingIntensity; 15] = [
+        LightingIntensity::C05mA,
+        LightingIntensity::C10mA,
+        LightingIntensity::C15mA,
+        LightingIntensity::C20mA,
+        LightingIntensity::C25mA,
+        LightingIntensity::C30mA,
+        LightingIntensity::C35mA,
+        LightingIntensity::C40mA,
+        LightingIntensity::C45mA,
+        LightingIntensity::C50mA,
+        LightingIntensity::C55mA,
+        LightingIntensity::C60mA,
+        LightingIntensity::C65mA,
+        LightingIntensity::C70mA,
+        LightingIntensity::C75mA,
+    ];
+
+    /// Position in `ALL`, from 0 for 5mA to 14 for 75mA.
+    fn step(&self) -> usize {
+        match *self {
+            LightingIntensity::C05mA => 0,
+            LightingIntensity::C10mA => 1,
+            LightingIntensity::C15mA => 2,
+            LightingIntensity::C20mA => 3,
+            LightingIntensity::C25mA => 4,
+            LightingIntensity::C30mA => 5,
+            LightingIntensity::C35mA => 6,
+            LightingIntensity::C40mA => 7,
+            LightingIntensity::C45mA => 8,
+            LightingIntensity::C50mA => 9,
+            LightingIntensity::C55mA => 10,
+            LightingIntensity::C60mA => 11,
+            LightingIntensity::C65mA => 12,
+            LightingIntensity::C70mA => 13,
+            LightingIntensity::C75mA => 14,
+        }
     }
 
-    fn write_i2c(&mut self, write: &[u8]) -> Result<(), DriverError<E>> {
-        self.i2c
-            .write(self.address, write)
-            .map_err(DriverError::I2C)
+    /// Next intensity, stays at 75mA instead of wrapping to 5mA.
+    pub fn brighter(&self) -> LightingIntensity {
+        Self::ALL[(self.step() + 1).min(Self::ALL.len() - 1)]
     }
 
-    fn write_config(&mut self, configuration: u8) -> Result<(), DriverError<E>> {
-        self.write_i2c(&[CONFIGURATION_ADDRESS, configuration])
+    /// Previous intensity, stays at 5mA instead of wrapping to 75mA.
+    pub fn dimmer(&self) -> LightingIntensity {
+        Self::ALL[self.step().saturating_sub(1)]
     }
 
-    /// Send configuration by I2C and persist a new configuration to this instance
-    fn update_lighting_effect(&mut self, configuration: u8) -> Result<(), DriverError<E>> {
-        if self.lighting_effects_register != configuration {
-            self.debug("lighting effect", configuration);
-            self.i2c
-                .write(self.address, &[LIGHTING_EFFECT_ADDRESS, configuration])
-                .map_err(DriverError::I2C)?;
-            self.lighting_effects_register = configuration
-        }
-        Ok(())
+    /// Current of the row in milliamps.
+    pub fn milliamps(&self) -> u8 {
+        (self.step() as u8 + 1) * 5
     }
 
-    /// Init
-    fn init(&mut self) -> Result<(), DriverError<E>> {
-        let audio_input_mask = if self.audio_input_enabled {
-            0b0_0000_1_00
-        } else {
-            0
-        };
-
-        let configuration: u8 = 0b0_0000_0_00 | audio_input_mask | self.dimensions as u8;
-
-        if configuration != self.configuration_register {
-            self.debug("configuration", configuration);
-            self.write_config(configuration)?;
-            self.configuration_register = configuration;
-        }
-
-        Ok(())
+    /// The nearest intensity to the current, saturated to 5mA..=75mA.
+    pub fn from_milliamps(milliamps: u8) -> LightingIntensity {
+        let step = (milliamps.saturating_add(2) / 5).clamp(1, Self::ALL.len() as u8);
+        Self::ALL[step as usize - 1]
     }
 
-    /// Counts of rows.
-    pub fn rows_count(&self) -> u8 {
-        return self.rows_count;
+    /// Percent of the highest intensity (75mA), rounded.
+    pub fn percent(&self) -> u8 {
+        ((self.milliamps() as u16 * 100 + 37) / 75) as u8
     }
 
-    /// Counts of columns.
-    pub fn columns_count(&self) -> u8 {
-        return self.columns_count;
+    /// The nearest intensity to the percent of the highest intensity (75mA).
+    /// Percents above 100 are saturated.
+    pub fn from_percent(percent: u8) -> LightingIntensity {
+        let milliamps = (percent.min(100) as u16 * 75 + 50) / 100;
+        Self::from_milliamps(milliamps as u8)
     }
+}
 
-    /// Update column data registers from temporary data registers.
-    pub fn update(&mut self) -> Result<(), DriverError<E>> {
-        self.debug("update", 0 as u8);
-        self.i2c
-            .write(self.address, &[UPDATE_COLUMN_ADDRESS, 0])
-            .map_err(DriverError::I2C)
-    }
+/// How a matrix is mounted: clockwise rotation or mirroring.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Orientation {
+    Rotate0,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Left and right are swapped.
+    MirrorHorizontal,
+    /// Top and bottom are swapped.
+    MirrorVertical,
+}
 
-    /// Send data to temporary registers.
-    /// <div class="warning">`row_number` starts from 1.</div>
-    pub fn send_column(&mut self, column_number: u8, column: u8) -> Result<(), DriverError<E>> {
-        if column_number > self.columns_count {
-            return Err(DriverError::invalid_column(
-                column_number,
-                self.columns_count,
-            ));
+impl Orientation {
+    /// Size of the rotated matrix.
+    pub fn size(&self, columns_count: u8, rows_count: u8) -> (u8, u8) {
+        match *self {
+            Orientation::Rotate90 | Orientation::Rotate270 => (rows_count, columns_count),
+            _ => (columns_count, rows_count),
         }
-        let msg = concat!("send column: ", stringify!(column_number));
-        self.debug(msg, column);
-        self.i2c
-            .write(self.address, &[column_number, column])
-            .map_err(DriverError::I2C)
     }
 
-    /// Send data to temporary register and update columns registers.
-    /// <div class="warning">`row_number` starts from 1.</div>
-    pub fn draw_column(&mut self, column_number: u8, column: u8) -> Result<(), DriverError<E>> {
-        self.send_column(column_number, column)?;
-        self.update()
-    }
-
-    /// Send data to temporary registers and update columns registers.
-    /// Picture is array of columns.
-    pub fn draw(&mut self, picture: &[u8]) -> Result<(), DriverError<E>> {
-        for (column_idx, column) in picture.iter().enumerate() {
-            let column_number = (column_idx + 1) as u8;
-            self.send_column(column_number, *column)?
+    /// Convert a point of the rotated matrix to the column (`x`) and row (`y`) of the chip.
+    /// `None` if the point is outside of the rotated matrix.
+    pub fn to_matrix(self, x: u8, y: u8, columns_count: u8, rows_count: u8) -> Option<(u8, u8)> {
+        let (width, height) = self.size(columns_count, rows_count);
+        if x >= width || y >= height {
+            return None;
         }
-        self.update()
-    }
-
-    /// Set intensity of led's matrix.
-    pub fn set_intensity(&mut self, intensity: LightingIntensity) -> Result<(), DriverError<E>> {
-        let mask = 0b1_111_0000;
-        let configuration = (self.lighting_effects_register & mask) | intensity as u8;
-
-        self.update_lighting_effect(configuration)?;
-
-        Ok(())
+        let point = match self {
+            Orientation::Rotate0 => (x, y),
+            Orientation::Rotate90 => (y, rows_count - 1 - x),
+            Orientation::Rotate180 => (columns_count - 1 - x, rows_count - 1 - y),
+            Orientation::Rotate270 => (columns_count - 1 - y, x),
+            Orientation::MirrorHorizontal => (columns_count - 1 - x, y),
+            Orientation::MirrorVertical => (x, rows_count - 1 - y),
+        };
+        Some(point)
     }
+}
 
-    /// Set audio input gain
-    pub fn set_audio_input_gain(&mut self, gain: AudioInputGain) -> Result<(), DriverError<E>> {
-        let mask = 0b1_000_1111;
-        let configuration = (self.lighting_effects_register & mask) | gain as u8;
-
-        self.update_lighting_effect(configuration)?;
+/// How the driver sends several columns to the chip.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WriteMode {
+    /// All columns in one I2C transaction, the chip increments register address after each byte.
+    Burst,
+    /// One I2C transaction per column.
+    PerColumn,
+}
 
-        Ok(())
-    }
+/// What the driver does when the chip may lose its registers.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResyncPolicy {
+    /// Call `resync` yourself, for example after a brown-out.
+    Manual,
+    /// The next write after an I2C error rewrites all registers and the frame first.
+    AfterError,
+}
 
-    /// Enable audio equalize
-    pub fn audio_eq_enable(&mut self) -> Result<(), DriverError<E>> {
-        let configuration = 0b0_1_000000;
-        self.debug("Enable audio eq", configuration);
-        self.write_i2c(&[AUDIO_EQ_ADDRESS, configuration])
-    }
+/// All supported Audio input gains
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum AudioInputGain {
+    G00dB = 0b0_000_0000,
+    G03dB = 0b0_001_0000,
+    G06dB = 0b0_010_0000,
+    G09dB = 0b0_011_0000,
+    G12dB = 0b0_100_0000,
+    G15dB = 0b0_101_0000,
+    G18dB = 0b0_110_0000,
+    GMinus6dB = 0b0_111_0000,
+}
 
-    /// Disable audio equalize
-    pub fn audio_eq_disable(&mut self) -> Result<(), DriverError<E>> {
-        let configuration = 0b0_0_000000;
-        self.debug("Disable audio eq", configuration);
-        self.write_i2c(&[AUDIO_EQ_ADDRESS, configuration])
-    }
+impl AudioInputGain {
+    /// All gains from the lowest.
+    pub const ALL: [AudioInputGain; 8] = [
+        AudioInputGain::GMinus6dB,
+        AudioInputGain::G00dB,
+        AudioInputGain::G03dB,
+        AudioInputGain::G06dB,
+        AudioInputGain::G09dB,
+        AudioInputGain::G12dB,
+        AudioInputGain::G15dB,
+        AudioInputGain::G18dB,
+    ];
+}
 
-    /// Send data to temporary registers and update columns registers.
-    /// Picture is array of rows.
-    ///
-    /// Use this method to simplify a work with led-matrix-editors like this one:
-    /// <https://xantorohara.github.io/led-matrix-editor/>
-    pub fn draw_bitmap(&mut self, picture: &[u8; 8]) -> Result<(), DriverError<E>> {
-        if self.dimensions != MatrixDimensions::M8x8 {
-            return Err(DriverError::IncorrectMatrixSize);
-        }
+/// What the chip shows.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisplayMode {
+    /// Columns from the registers.
+    Matrix,
+    /// Bars of the audio frequency equalizer, column registers are ignored.
+    Equalizer,
+}
 
-        let mut column_mask: u8 = 0b1000_0000;
-        for column_idx in 0..=7 {
-            let mut column: u8 = 0;
-            for row_idx in 0..=7 {
-                let pixel = picture[row_idx] & column_mask;
-                let pixel_in_column = if column_idx < row_idx {
-                    pixel >> (row_idx - column_idx)
-                } else {
-                    pixel << (column_idx - row_idx)
-                };
-                column = column | pixel_in_column;
-            }
-            self.send_column((column_idx as u8) + 1, column)?;
-            column_mask = column_mask >> 1;
-        }
+/// Audio related configuration of the chip.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AudioMode {
+    /// Brightness of the picture is modulated by the audio input.
+    pub input_enabled: bool,
+    pub display_mode: DisplayMode,
+    pub gain: AudioInputGain,
+}
 
-        self.update()
+impl AudioMode {
+    /// Matrix display mode without audio, the state after power on.
+    pub const OFF: AudioMode = AudioMode {
+        input_enabled: false,
+        display_mode: DisplayMode::Matrix,
+        gain: DEFAULT_AUDIO_INPUT_GAIN,
+    };
+
+    /// Picture modulated by the audio input.
+    pub const MODULATED: AudioMode = AudioMode {
+        input_enabled: true,
+        display_mode: DisplayMode::Matrix,
+        gain: DEFAULT_AUDIO_INPUT_GAIN,
+    };
+
+    /// Equalizer bars of the audio input.
+    pub const EQUALIZER: AudioMode = AudioMode {
+        input_enabled: true,
+        display_mode: DisplayMode::Equalizer,
+        gain: DEFAULT_AUDIO_INPUT_GAIN,
+    };
+
+    pub fn with_gain(self, gain: AudioInputGain) -> Self {
+        AudioMode { gain, ..self }
     }
+}
 
-    /// Set all led's to off. If you want just turn off matrix without
-    /// changing picture, use `software_shutdown`
-    pub fn clear(&mut self) -> Result<(), DriverError<E>> {
-        for i in 1..=self.columns_count {
-            self.send_column(i, 0)?;
-        }
-        self.update()
+impl Default for AudioMode {
+    fn default() -> Self {
+        AudioMode::OFF
     }
+}
 
-    /// Set all led's to on
-    pub fn fill(&mut self) -> Result<(), DriverError<E>> {
-        for i in 1..=self.columns_count {
-            // if rows count less then 8, older bit will be ignored
-            self.send_column(i, 0b1111_1111)?;
-        }
-        self.update()
-    }
+pub const MAX_COLUMNS: usize = 11;
 
-    /// Turn off matrix output with saving all registry.
-    /// Use `software_on` to return image
-    pub fn software_shutdown(&mut self) -> Result<(), DriverError<E>> {
-        let configuration = self.configuration_register | 0b1_0000_0_00;
-        self.write_config(configuration)
-    }
+pub const DEFAULT_LIGHTING_INTENSITY: LightingIntensity = LightingIntensity::C40mA;
+pub const DEFAULT_AUDIO_INPUT_GAIN: AudioInputGain = AudioInputGain::G00dB;
 
-    /// Turn on matrix output
-    pub fn software_on(&mut self) -> Result<(), DriverError<E>> {
-        let configuration = self.configuration_register & 0b0_1111_1_11;
-        self.write_config(configuration)
-    }
-}
+driver!(
+    /// Driver
+    IS31FL3728,
+    [],
+    []
+);
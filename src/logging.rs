@@ -0,0 +1,66 @@
+//! Logging facade: drivers call these macros, the backend is selected by features.
+//!
+//! * `defmt` - log frames are encoded by [defmt](https://defmt.ferrous-systems.com/),
+//!   strings are not formatted on the target.
+//! * `rtt-debug` - text over RTT by `rtt_target::debug_rprintln!`.
+//!
+//! Without a backend the macros are compiled out. To add a backend, add its branch here,
+//! call sites don't change.
+
+/// Log point of every I2C write: address of the chip, register address and data bytes.
+macro_rules! log_write {
+    ($address:expr, $write:expr) => {{
+        let address: u8 = $address;
+        let write: &[u8] = $write;
+        if let Some((register, data)) = write.split_first() {
+            #[cfg(feature = "defmt")]
+            defmt::debug!(
+                "IS31FL3728[{=u8:#x}]: register {=u8:#x} <- {=[u8]:#010b}",
+                address,
+                *register,
+                data
+            );
+            #[cfg(feature = "rtt-debug")]
+            rtt_target::debug_rprintln!(
+                "IS31FL3728[0x{:02x}]: register 0x{:02x} <- {:02x?}",
+                address,
+                register,
+                data
+            );
+            let _ = (address, register, data);
+        }
+    }};
+}
+
+/// Log point of a failed I2C write: address of the chip and register address.
+macro_rules! log_write_error {
+    ($address:expr, $register:expr) => {{
+        let address: u8 = $address;
+        let register: u8 = $register;
+        #[cfg(feature = "defmt")]
+        defmt::warn!(
+            "IS31FL3728[{=u8:#x}]: write of register {=u8:#x} failed",
+            address,
+            register
+        );
+        #[cfg(feature = "rtt-debug")]
+        rtt_target::debug_rprintln!(
+            "IS31FL3728[0x{:02x}]: write of register 0x{:02x} failed",
+            address,
+            register
+        );
+        let _ = (address, register);
+    }};
+}
+
+/// Log point of an operation which sends several registers: address of the chip and a message.
+macro_rules! log_event {
+    ($address:expr, $msg:literal) => {{
+        let address: u8 = $address;
+        #[cfg(feature = "defmt")]
+        defmt::debug!("IS31FL3728[{=u8:#x}]: {=str}", address, $msg);
+        #[cfg(feature = "rtt-debug")]
+        rtt_target::debug_rprintln!("IS31FL3728[0x{:02x}]: {}", address, $msg);
+        let _ = address;
+    }};
+}
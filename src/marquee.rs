@@ -0,0 +1,171 @@
+//! Scrolling text.
+//!
+//! [`Marquee`] keeps columns of the text and the position of the visible window. Drivers and
+//! [`TiledDisplay`](crate::TiledDisplay) draw the window and move it by one column per step:
+//! ```no_run
+//! # use is31fl3728_rs::{Address, Marquee, MatrixDimensions, IS31FL3728};
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! # let mut led_matrix = IS31FL3728::new(i2c, Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+//! # fn millis() -> u32 { 0 }
+//! let mut marquee: Marquee<128> = Marquee::new("Hello, world!", led_matrix.font())
+//!     .unwrap()
+//!     .looped(true);
+//! loop {
+//!     led_matrix.marquee_tick(&mut marquee, millis()).unwrap();
+//! }
+//! ```
+
+use crate::font::Font;
+
+/// Default period of steps of `marquee_tick`.
+pub const DEFAULT_STEP_PERIOD_MS: u32 = 100;
+
+/// Where the text moves.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    /// Text comes from the right edge, usual direction for reading.
+    Left,
+    /// Text comes from the left edge.
+    Right,
+}
+
+/// Text which scrolls across the display, up to `N` columns.
+#[derive(Clone, Debug)]
+pub struct Marquee<const N: usize> {
+    /// Columns of the text, bit 0 is the top row.
+    columns: [u8; N],
+    len: u16,
+    height: u8,
+    direction: Direction,
+    looped: bool,
+    gap: u16,
+    step_period_ms: u32,
+    last_step_ms: Option<u32>,
+    /// Count of steps, the window starts on empty columns before the text.
+    position: u32,
+}
+
+impl<const N: usize> Marquee<N> {
+    /// Precompute columns of the text. Returns `None` if they don't fit to `N` columns.
+    pub fn new(text: &str, font: &Font) -> Option<Self> {
+        let len = font.text_width(text);
+        if len > N || len > u16::MAX as usize {
+            return None;
+        }
+        let mut columns = [0; N];
+        for (column, data) in columns.iter_mut().zip(font.text_columns(text)) {
+            *column = data;
+        }
+
+        Some(Marquee {
+            columns,
+            len: len as u16,
+            height: font.height,
+            direction: Direction::Left,
+            looped: false,
+            gap: font.width as u16 + 1,
+            step_period_ms: DEFAULT_STEP_PERIOD_MS,
+            last_step_ms: None,
+            position: 0,
+        })
+    }
+
+    pub fn direction(self, direction: Direction) -> Self {
+        Marquee { direction, ..self }
+    }
+
+    /// Repeat the text endlessly. Otherwise scrolling is finished when the text leaves the display.
+    pub fn looped(self, looped: bool) -> Self {
+        Marquee { looped, ..self }
+    }
+
+    /// Empty columns between repeats of looped text, one glyph by default.
+    pub fn gap(self, gap: u16) -> Self {
+        Marquee { gap, ..self }
+    }
+
+    /// Period of steps of `marquee_tick`, it sets the speed of scrolling.
+    pub fn step_period(self, step_period_ms: u32) -> Self {
+        Marquee {
+            step_period_ms,
+            ..self
+        }
+    }
+
+    /// Change the speed of scrolling on the fly.
+    pub fn set_step_period(&mut self, step_period_ms: u32) {
+        self.step_period_ms = step_period_ms;
+    }
+
+    /// Height of the text in rows.
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    /// Start scrolling from the beginning.
+    pub fn reset(&mut self) {
+        self.position = 0;
+        self.last_step_ms = None;
+    }
+
+    /// Is the text left the display with the width. Looped text is never finished.
+    pub fn is_finished(&self, width: u16) -> bool {
+        !self.looped && self.position >= width as u32 + self.len as u32
+    }
+
+    /// Move the window by one column. Returns `false` if scrolling is finished.
+    pub(crate) fn advance(&mut self, width: u16) -> bool {
+        if self.is_finished(width) {
+            return false;
+        }
+        self.position += 1;
+        let period = self.len as u32 + self.gap as u32;
+        if self.looped && self.position >= width as u32 + period {
+            self.position -= period;
+        }
+        true
+    }
+
+    /// Is it time for the next step at `now_ms`, the first call is always a step.
+    pub(crate) fn is_due(&mut self, now_ms: u32) -> bool {
+        match self.last_step_ms {
+            Some(last_step_ms) if now_ms.wrapping_sub(last_step_ms) < self.step_period_ms => false,
+            _ => {
+                self.last_step_ms = Some(now_ms);
+                true
+            }
+        }
+    }
+
+    /// Column of the window with the width, bit 0 is the top row.
+    pub(crate) fn window_column(&self, x: u16, width: u16) -> u8 {
+        if self.len == 0 {
+            return 0;
+        }
+        let idx = match self.direction {
+            Direction::Left => x,
+            Direction::Right => width - 1 - x,
+        };
+        // columns before the text are empty, so the text comes from the edge
+        let Some(offset) = (self.position + idx as u32).checked_sub(width as u32) else {
+            return 0;
+        };
+        let offset = if self.looped {
+            offset % (self.len as u32 + self.gap as u32)
+        } else {
+            offset
+        };
+        if offset >= self.len as u32 {
+            return 0;
+        }
+        match self.direction {
+            Direction::Left => self.columns[offset as usize],
+            Direction::Right => self.columns[(self.len as u32 - 1 - offset) as usize],
+        }
+    }
+
+    /// Row of the display for the top row of the text, the text is centered vertically.
+    pub(crate) fn top(&self, height: u16) -> u16 {
+        height.saturating_sub(self.height as u16) / 2
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{row_mask, MAX_COLUMNS};
+
+/// Wiring of LEDs to row and column pins of the chip.
+///
+/// LED modules connect pins of the chip to LEDs in different orders. The driver applies the map
+/// to every column before sending it, so pictures are drawn as if LEDs were wired in order.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PixelMap {
+    /// `columns[i]` is the column of the chip (zero-based) wired to the `i`-th column of LEDs.
+    pub columns: [u8; MAX_COLUMNS],
+    /// `rows[i]` is the row of the chip (zero-based) wired to the `i`-th row of LEDs.
+    pub rows: [u8; 8],
+    /// Row 0 of the chip is the youngest bit of a column instead of the oldest used one.
+    pub reversed_bits: bool,
+}
+
+impl PixelMap {
+    /// LEDs are wired in order.
+    pub const IDENTITY: PixelMap = PixelMap {
+        columns: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+        rows: [0, 1, 2, 3, 4, 5, 6, 7],
+        reversed_bits: false,
+    };
+
+    /// Column bytes are sent with the reversed bit order, the first row is the youngest bit.
+    pub const REVERSED_BITS: PixelMap = PixelMap {
+        reversed_bits: true,
+        ..Self::IDENTITY
+    };
+
+    /// Modules with 8 columns wired from right to left.
+    pub const REVERSED_COLUMNS_8: PixelMap = PixelMap {
+        columns: [7, 6, 5, 4, 3, 2, 1, 0, 8, 9, 10],
+        ..Self::IDENTITY
+    };
+
+    /// Modules with 8 rows wired from bottom to top.
+    pub const REVERSED_ROWS_8: PixelMap = PixelMap {
+        rows: [7, 6, 5, 4, 3, 2, 1, 0],
+        ..Self::IDENTITY
+    };
+
+    /// 8x8 modules where both rows and columns are wired in reverse.
+    pub const REVERSED_8X8: PixelMap = PixelMap {
+        columns: [7, 6, 5, 4, 3, 2, 1, 0, 8, 9, 10],
+        rows: [7, 6, 5, 4, 3, 2, 1, 0],
+        reversed_bits: false,
+    };
+
+    /// Check that the first columns and rows of the map are permutations of the chip's pins.
+    pub fn is_valid(&self, columns_count: u8, rows_count: u8) -> bool {
+        let mut used_columns: u16 = 0;
+        for column in &self.columns[..columns_count as usize] {
+            if *column >= columns_count || used_columns & (1 << column) != 0 {
+                return false;
+            }
+            used_columns |= 1 << column;
+        }
+
+        let mut used_rows: u8 = 0;
+        for row in &self.rows[..rows_count as usize] {
+            if *row >= rows_count || used_rows & (1 << row) != 0 {
+                return false;
+            }
+            used_rows |= 1 << row;
+        }
+        true
+    }
+
+    /// Column of the chip (zero-based) wired to the column of LEDs.
+    pub(crate) fn column(&self, column_idx: u8) -> u8 {
+        self.columns[column_idx as usize]
+    }
+
+    /// Bit of the chip's column wired to the row of LEDs, the chip has `rows_count` rows.
+    pub(crate) fn row_mask(&self, row_idx: u8, rows_count: u8) -> u8 {
+        let row = self.rows[row_idx as usize];
+        if self.reversed_bits {
+            1 << row
+        } else {
+            row_mask(rows_count, row)
+        }
+    }
+
+    /// Convert a column of LEDs to the column of the chip's register.
+    pub(crate) fn column_data(&self, column: u8, rows_count: u8) -> u8 {
+        if *self == Self::IDENTITY {
+            return column;
+        }
+        (0..rows_count)
+            .filter(|row_idx| column & row_mask(rows_count, *row_idx) != 0)
+            .fold(0, |data, row_idx| data | self.row_mask(row_idx, rows_count))
+    }
+}
+
+impl Default for PixelMap {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
@@ -0,0 +1,140 @@
+//! Copy of the chip's registers.
+//!
+//! Registers of the chip are write-only, so the driver remembers everything it has written
+//! successfully. Get it with `registers()` of the driver to query the state of the chip
+//! without tracking it in parallel.
+
+use crate::state::{
+    AUDIO_EQ_ADDRESS, AUDIO_EQ_MASK, AUDIO_INPUT_GAIN_MASK, AUDIO_INPUT_MASK,
+    CONFIGURATION_ADDRESS, INTENSITY_MASK, LIGHTING_EFFECT_ADDRESS, RESET_ADDRESS, SHUTDOWN_MASK,
+    UPDATE_COLUMN_ADDRESS,
+};
+use crate::{
+    AudioInputGain, AudioMode, DisplayMode, LightingIntensity, MatrixDimensions,
+    DEFAULT_AUDIO_INPUT_GAIN, MAX_COLUMNS,
+};
+
+/// Values of all writable registers of one chip.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct RegisterShadow {
+    configuration: u8,
+    /// Column registers which are written, but not updated yet.
+    pending: [u8; MAX_COLUMNS],
+    /// Column registers which are shown, update copies pending registers here.
+    latched: [u8; MAX_COLUMNS],
+    lighting_effect: u8,
+    audio_eq: u8,
+}
+
+impl RegisterShadow {
+    /// All registers are 0 like after power on.
+    pub const POWER_ON: RegisterShadow = RegisterShadow {
+        configuration: 0,
+        pending: [0; MAX_COLUMNS],
+        latched: [0; MAX_COLUMNS],
+        lighting_effect: 0,
+        audio_eq: 0,
+    };
+
+    /// Apply the I2C write: register address followed by data,
+    /// the chip increments the address after each byte.
+    pub(crate) fn write(&mut self, command: &[u8]) {
+        let Some((register, data)) = command.split_first() else {
+            return;
+        };
+        for (offset, byte) in data.iter().enumerate() {
+            self.write_register(register.wrapping_add(offset as u8), *byte);
+        }
+    }
+
+    pub(crate) fn write_register(&mut self, register: u8, data: u8) {
+        match register {
+            CONFIGURATION_ADDRESS => self.configuration = data,
+            0x01..=0x0B => self.pending[register as usize - 1] = data,
+            UPDATE_COLUMN_ADDRESS => self.latched = self.pending,
+            LIGHTING_EFFECT_ADDRESS => self.lighting_effect = data,
+            AUDIO_EQ_ADDRESS => self.audio_eq = data,
+            RESET_ADDRESS => *self = RegisterShadow::POWER_ON,
+            // other addresses are not used by the chip
+            _ => {}
+        }
+    }
+
+    /// Raw value of the register, `UPDATE_COLUMN_ADDRESS` and unused addresses are 0.
+    pub fn register(&self, address: u8) -> u8 {
+        match address {
+            CONFIGURATION_ADDRESS => self.configuration,
+            0x01..=0x0B => self.pending[address as usize - 1],
+            LIGHTING_EFFECT_ADDRESS => self.lighting_effect,
+            AUDIO_EQ_ADDRESS => self.audio_eq,
+            _ => 0,
+        }
+    }
+
+    pub fn configuration(&self) -> u8 {
+        self.configuration
+    }
+
+    pub fn lighting_effect(&self) -> u8 {
+        self.lighting_effect
+    }
+
+    pub fn audio_eq(&self) -> u8 {
+        self.audio_eq
+    }
+
+    /// Column registers which are written, but not updated yet.
+    pub fn pending_columns(&self) -> &[u8; MAX_COLUMNS] {
+        &self.pending
+    }
+
+    /// Column registers which are shown.
+    pub fn latched_columns(&self) -> &[u8; MAX_COLUMNS] {
+        &self.latched
+    }
+
+    /// Array mode from the configuration register.
+    pub fn dimensions(&self) -> MatrixDimensions {
+        match self.configuration & 0b11 {
+            0b00 => MatrixDimensions::M8x8,
+            0b01 => MatrixDimensions::M7x9,
+            0b10 => MatrixDimensions::M6x10,
+            _ => MatrixDimensions::M5x11,
+        }
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.configuration & SHUTDOWN_MASK != 0
+    }
+
+    /// Intensity from the lighting effect register, `None` for the reserved value.
+    pub fn intensity(&self) -> Option<LightingIntensity> {
+        let bits = self.lighting_effect & INTENSITY_MASK;
+        LightingIntensity::ALL
+            .into_iter()
+            .find(|intensity| *intensity as u8 == bits)
+    }
+
+    /// Gain from the lighting effect register.
+    pub fn audio_gain(&self) -> AudioInputGain {
+        let bits = self.lighting_effect & AUDIO_INPUT_GAIN_MASK;
+        AudioInputGain::ALL
+            .into_iter()
+            .find(|gain| *gain as u8 == bits)
+            .unwrap_or(DEFAULT_AUDIO_INPUT_GAIN)
+    }
+
+    /// Audio input from the configuration register, display mode from the audio EQ register.
+    pub fn audio_mode(&self) -> AudioMode {
+        let display_mode = if self.audio_eq & AUDIO_EQ_MASK != 0 {
+            DisplayMode::Equalizer
+        } else {
+            DisplayMode::Matrix
+        };
+        AudioMode {
+            input_enabled: self.configuration & AUDIO_INPUT_MASK != 0,
+            display_mode,
+            gain: self.audio_gain(),
+        }
+    }
+}
@@ -0,0 +1,401 @@
+//! Register map and driver state shared by the blocking and the async drivers.
+//!
+//! Everything here is pure: methods compute register values, drivers send them
+//! over their I2C bus and commit the result back to the state.
+
+use core::fmt::Debug;
+use core::ops::Range;
+
+#[cfg(feature = "font")]
+use crate::font::Font;
+use crate::geometry::Rows;
+#[cfg(feature = "font")]
+use crate::marquee::Marquee;
+use crate::shadow::RegisterShadow;
+use crate::{
+    row_mask, Address, AudioInputGain, DisplayMode, DriverError, LightingIntensity,
+    MatrixDimensions, Orientation, PixelMap, ResyncPolicy, WriteMode, MAX_COLUMNS,
+};
+
+pub(crate) const CONFIGURATION_ADDRESS: u8 = 0x00;
+pub(crate) const UPDATE_COLUMN_ADDRESS: u8 = 0x0C;
+pub(crate) const LIGHTING_EFFECT_ADDRESS: u8 = 0x0D;
+pub(crate) const AUDIO_EQ_ADDRESS: u8 = 0x0F;
+pub(crate) const RESET_ADDRESS: u8 = 0xFF;
+
+pub(crate) const SHUTDOWN_MASK: u8 = 0b1_0000_0_00;
+pub(crate) const AUDIO_INPUT_MASK: u8 = 0b0_0000_1_00;
+pub(crate) const INTENSITY_MASK: u8 = 0b0_000_1111;
+pub(crate) const AUDIO_INPUT_GAIN_MASK: u8 = 0b0_111_0000;
+
+pub(crate) const AUDIO_EQ_ENABLED: u8 = 0b0_1_000000;
+pub(crate) const AUDIO_EQ_DISABLED: u8 = 0b0_0_000000;
+pub(crate) const AUDIO_EQ_MASK: u8 = 0b0_1_000000;
+
+/// Configuration and cached registers of one chip.
+pub(crate) struct State {
+    pub(crate) address: u8,
+    pub(crate) rows_count: u8,
+    pub(crate) columns_count: u8,
+    /// Registers which are written to the chip.
+    pub(crate) registers: RegisterShadow,
+    /// Framebuffer, one byte per column of the chip, as it is sent to the chip.
+    pub(crate) frame: [u8; MAX_COLUMNS],
+    /// Bit `n` is set when column `n` (zero-based) has changed since the last flush.
+    pub(crate) dirty: u16,
+    pub(crate) write_mode: WriteMode,
+    pub(crate) resync_policy: ResyncPolicy,
+    /// A write has failed, so registers of the chip may differ from the shadow.
+    pub(crate) resync_needed: bool,
+    pub(crate) orientation: Orientation,
+    pub(crate) pixel_map: PixelMap,
+}
+
+impl State {
+    pub(crate) fn new<E: Debug>(
+        address: Address,
+        matrix_dimensions: MatrixDimensions,
+        audio_input_enabled: bool,
+    ) -> Result<Self, DriverError<E>> {
+        let address = Self::check_address(address)?;
+
+        let rows_count = matrix_dimensions.rows_count();
+        let columns_count = matrix_dimensions.columns_count();
+
+        let audio_input_mask = if audio_input_enabled {
+            AUDIO_INPUT_MASK
+        } else {
+            0
+        };
+
+        let mut registers = RegisterShadow::POWER_ON;
+        // sent by init, the chip may keep the configuration from the previous run
+        registers.write(&[
+            CONFIGURATION_ADDRESS,
+            audio_input_mask | matrix_dimensions as u8,
+        ]);
+
+        Ok(State {
+            address,
+            rows_count,
+            columns_count,
+            registers,
+            frame: [0; MAX_COLUMNS],
+            // the content of the chip is unknown, so the first flush sends all columns
+            dirty: (1 << columns_count) - 1,
+            write_mode: WriteMode::Burst,
+            resync_policy: ResyncPolicy::Manual,
+            resync_needed: false,
+            orientation: Orientation::Rotate0,
+            pixel_map: PixelMap::IDENTITY,
+        })
+    }
+
+    pub(crate) fn check_address<E: Debug>(address: Address) -> Result<u8, DriverError<E>> {
+        if !address.is_valid() {
+            return Err(DriverError::InvalidAddress(address.value()));
+        }
+        Ok(address.value())
+    }
+
+    /// Commit the write to the shadow if it was sent.
+    pub(crate) fn written<E: Debug>(&mut self, write: &[u8], result: &Result<(), DriverError<E>>) {
+        match result {
+            Ok(()) => self.registers.write(write),
+            Err(_) => self.resync_needed = self.resync_policy == ResyncPolicy::AfterError,
+        }
+    }
+
+    /// Registers and the framebuffer after the reset register was written.
+    /// The configuration with the array mode is sent by init.
+    pub(crate) fn reset(&mut self, dimensions: MatrixDimensions) {
+        self.registers = RegisterShadow::POWER_ON;
+        self.registers
+            .write(&[CONFIGURATION_ADDRESS, dimensions as u8]);
+        self.frame = [0; MAX_COLUMNS];
+        self.dirty = 0;
+        self.resync_needed = false;
+    }
+
+    pub(crate) fn check_frame_length<E: Debug>(&self, length: usize) -> Result<(), DriverError<E>> {
+        if length > self.columns_count as usize {
+            return Err(DriverError::FrameLengthMismatch(
+                length,
+                self.columns_count as usize,
+            ));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn intensity_configuration(&self, intensity: LightingIntensity) -> u8 {
+        (self.registers.lighting_effect() & !INTENSITY_MASK) | intensity as u8
+    }
+
+    pub(crate) fn audio_input_gain_configuration(&self, gain: AudioInputGain) -> u8 {
+        (self.registers.lighting_effect() & !AUDIO_INPUT_GAIN_MASK) | gain as u8
+    }
+
+    pub(crate) fn audio_input_configuration(&self, enabled: bool) -> u8 {
+        if enabled {
+            self.registers.configuration() | AUDIO_INPUT_MASK
+        } else {
+            self.registers.configuration() & !AUDIO_INPUT_MASK
+        }
+    }
+
+    pub(crate) fn audio_eq_configuration(display_mode: DisplayMode) -> u8 {
+        match display_mode {
+            DisplayMode::Matrix => AUDIO_EQ_DISABLED,
+            DisplayMode::Equalizer => AUDIO_EQ_ENABLED,
+        }
+    }
+
+    pub(crate) fn shutdown_configuration(&self) -> u8 {
+        self.registers.configuration() | SHUTDOWN_MASK
+    }
+
+    pub(crate) fn on_configuration(&self) -> u8 {
+        self.registers.configuration() & !SHUTDOWN_MASK
+    }
+
+    /// Set column (zero-based) of LEDs in the framebuffer.
+    /// Returns the column of the chip which is wired to it.
+    pub(crate) fn set_frame_column(&mut self, column_idx: u8, column: u8) -> u8 {
+        let wire_idx = self.pixel_map.column(column_idx);
+        self.set_wire_column(
+            wire_idx,
+            self.pixel_map.column_data(column, self.rows_count),
+        );
+        wire_idx
+    }
+
+    /// Set consecutive columns of LEDs starting from `first_column_idx` in the framebuffer,
+    /// they are marked dirty even if they are not changed.
+    /// Returns the range of the chip's columns which are wired to them.
+    pub(crate) fn set_frame_columns(&mut self, first_column_idx: u8, columns: &[u8]) -> Range<u8> {
+        let mut start = u8::MAX;
+        let mut end = 0;
+        for (offset, column) in columns.iter().enumerate() {
+            let wire_idx = self.set_frame_column(first_column_idx + offset as u8, *column);
+            self.mark_dirty(wire_idx);
+            start = start.min(wire_idx);
+            end = end.max(wire_idx + 1);
+        }
+        start..end
+    }
+
+    /// Set column (zero-based) of the chip in the framebuffer, marks it dirty if it was changed.
+    fn set_wire_column(&mut self, wire_idx: u8, data: u8) {
+        let idx = wire_idx as usize;
+        if self.frame[idx] != data {
+            self.frame[idx] = data;
+            self.dirty |= 1 << wire_idx;
+        }
+    }
+
+    /// Mark column of the chip (zero-based) to be sent by the next flush.
+    pub(crate) fn mark_dirty(&mut self, wire_idx: u8) {
+        self.dirty |= 1 << wire_idx;
+    }
+
+    pub(crate) fn is_dirty(&self, wire_idx: u8) -> bool {
+        self.dirty & (1 << wire_idx) != 0
+    }
+
+    /// Mark columns of the chip as sent.
+    pub(crate) fn wire_sent(&mut self, range: Range<u8>) {
+        for wire_idx in range {
+            self.dirty &= !(1 << wire_idx);
+        }
+    }
+
+    pub(crate) fn set_pixel_map<E: Debug>(
+        &mut self,
+        pixel_map: PixelMap,
+    ) -> Result<(), DriverError<E>> {
+        if !pixel_map.is_valid(self.columns_count, self.rows_count) {
+            return Err(DriverError::InvalidPixelMap);
+        }
+        self.pixel_map = pixel_map;
+        self.frame = [0; MAX_COLUMNS];
+        self.dirty = (1 << self.columns_count) - 1;
+        Ok(())
+    }
+
+    /// Zero-based range of columns from the first dirty column to the last dirty column.
+    pub(crate) fn dirty_range(&self) -> Option<Range<u8>> {
+        if self.dirty == 0 {
+            return None;
+        }
+        let start = self.dirty.trailing_zeros() as u8;
+        let end = (u16::BITS - self.dirty.leading_zeros()) as u8;
+        Some(start..end)
+    }
+
+    /// Check that consecutive columns starting from `first_column_number` fit to the matrix.
+    pub(crate) fn check_columns<E: Debug>(
+        &self,
+        first_column_number: u8,
+        count: usize,
+    ) -> Result<(), DriverError<E>> {
+        if first_column_number == 0 {
+            return Err(DriverError::ColumnOutOfRange(0, self.columns_count));
+        }
+        let last_column_number = first_column_number as usize + count - 1;
+        if last_column_number > self.columns_count as usize {
+            return Err(DriverError::ColumnOutOfRange(
+                u8::try_from(last_column_number).unwrap_or(u8::MAX),
+                self.columns_count,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Register address followed by columns, the chip increments the address after each byte.
+    pub(crate) fn burst_command(
+        first_column_number: u8,
+        columns: &[u8],
+    ) -> ([u8; MAX_COLUMNS + 1], usize) {
+        let mut command = [0u8; MAX_COLUMNS + 1];
+        command[0] = first_column_number;
+        command[1..=columns.len()].copy_from_slice(columns);
+        (command, columns.len() + 1)
+    }
+
+    /// Copy columns of picture to the framebuffer.
+    pub(crate) fn set_frame<E: Debug>(&mut self, picture: &[u8]) -> Result<(), DriverError<E>> {
+        if self.orientation == Orientation::Rotate0 {
+            self.check_frame_length(picture.len())?;
+            for (column_idx, column) in picture.iter().enumerate() {
+                self.set_frame_column(column_idx as u8, *column);
+            }
+            return Ok(());
+        }
+
+        let (width, height) = self.size();
+        // a rotated column of non-square matrix doesn't fit to a byte,
+        // the combination is rejected for frames of any length
+        if height > 8 {
+            return Err(DriverError::UnsupportedMode);
+        }
+        if picture.len() > width as usize {
+            return Err(DriverError::FrameLengthMismatch(
+                picture.len(),
+                width as usize,
+            ));
+        }
+        for (x, column) in picture.iter().enumerate() {
+            for y in 0..height {
+                self.set_pixel(x as u8, y, column & row_mask(height, y) != 0);
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy row-major bitmap to the framebuffer.
+    pub(crate) fn set_bitmap<E: Debug, B: Rows + ?Sized>(
+        &mut self,
+        picture: &B,
+    ) -> Result<(), DriverError<E>> {
+        let (width, height) = self.size();
+        if picture.rows_count() != height as usize {
+            return Err(DriverError::FrameLengthMismatch(
+                picture.rows_count(),
+                height as usize,
+            ));
+        }
+        // `u8` rows can't hold 9-11 pixels, don't cut them
+        if picture.row_bits() < width {
+            return Err(DriverError::BitmapRowTooNarrow(picture.row_bits(), width));
+        }
+
+        for y in 0..height {
+            let row = picture.row(y as usize);
+            for x in 0..width {
+                let column_mask: u16 = 1 << (width - 1 - x);
+                self.set_pixel(x, y, row & column_mask != 0);
+            }
+        }
+        Ok(())
+    }
+
+    /// Set all columns of the framebuffer to the same value.
+    pub(crate) fn fill_frame(&mut self, column: u8) {
+        for column_idx in 0..self.columns_count {
+            self.set_frame_column(column_idx, column);
+        }
+    }
+
+    /// Width and height of the matrix according to the orientation.
+    pub(crate) fn size(&self) -> (u8, u8) {
+        self.orientation.size(self.columns_count, self.rows_count)
+    }
+
+    /// Column and row of the chip which show the pixel.
+    fn to_matrix(&self, x: u8, y: u8) -> Option<(u8, u8)> {
+        self.orientation
+            .to_matrix(x, y, self.columns_count, self.rows_count)
+    }
+
+    /// Column of the chip and mask of the bit which are wired to the pixel.
+    fn to_wire(&self, x: u8, y: u8) -> Option<(u8, u8)> {
+        let (column_idx, row_idx) = self.to_matrix(x, y)?;
+        Some((
+            self.pixel_map.column(column_idx),
+            self.pixel_map.row_mask(row_idx, self.rows_count),
+        ))
+    }
+
+    pub(crate) fn set_pixel(&mut self, x: u8, y: u8, on: bool) {
+        if let Some((wire_idx, mask)) = self.to_wire(x, y) {
+            let data = self.frame[wire_idx as usize];
+            let data = if on { data | mask } else { data & !mask };
+            self.set_wire_column(wire_idx, data);
+        }
+    }
+
+    pub(crate) fn get_pixel(&self, x: u8, y: u8) -> bool {
+        match self.to_wire(x, y) {
+            Some((wire_idx, mask)) => self.frame[wire_idx as usize] & mask != 0,
+            None => false,
+        }
+    }
+
+    /// Copy glyphs of the text to the framebuffer, the top left corner is at `x` and `y`.
+    /// Columns of glyphs are drawn with "off" pixels, glyphs outside of the matrix are cut.
+    #[cfg(feature = "font")]
+    pub(crate) fn set_text(&mut self, x: u8, y: u8, text: &str, font: &Font) {
+        let (width, _) = self.size();
+        let columns = font
+            .text_columns(text)
+            .take(width.saturating_sub(x) as usize);
+        for (offset, column) in columns.enumerate() {
+            self.set_glyph_column(x + offset as u8, y, column, font.height);
+        }
+    }
+
+    /// Draw the visible window of the marquee over the whole width.
+    #[cfg(feature = "font")]
+    pub(crate) fn set_marquee<const N: usize>(&mut self, marquee: &Marquee<N>) {
+        let (width, height) = self.size();
+        let y = marquee.top(height as u16) as u8;
+        for x in 0..width {
+            let column = marquee.window_column(x as u16, width as u16);
+            self.set_glyph_column(x, y, column, marquee.height());
+        }
+    }
+
+    /// Set `height` pixels down from `x` and `y`, bit 0 of the column is the top pixel.
+    #[cfg(feature = "font")]
+    fn set_glyph_column(&mut self, x: u8, y: u8, column: u8, height: u8) {
+        for row_idx in 0..height {
+            let on = column & (1 << row_idx) != 0;
+            self.set_pixel(x, y.saturating_add(row_idx), on);
+        }
+    }
+
+    pub(crate) fn toggle_pixel(&mut self, x: u8, y: u8) {
+        let on = self.get_pixel(x, y);
+        self.set_pixel(x, y, !on);
+    }
+}
@@ -0,0 +1,171 @@
+//! Preview of emulated matrices in a terminal.
+//!
+//! [`Renderer`] draws visible LEDs of [`Emulator`]s with ASCII or Unicode blocks, the intensity
+//! and the shutdown state. In live mode every `show` redraws the previous picture in place,
+//! so animations are played in the terminal:
+//! ```no_run
+//! # use std::thread;
+//! # use std::time::Duration;
+//! # use is31fl3728_rs::emulator::Emulator;
+//! # use is31fl3728_rs::terminal::{Renderer, Style};
+//! # use is31fl3728_rs::{Address, Animation, Frame, MatrixDimensions, IS31FL3728};
+//! # let emulator = Emulator::new(0x60);
+//! # let mut led_matrix =
+//! #     IS31FL3728::new(emulator.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+//! # let frames = [Frame::new(&[0xFF; 8], 100)];
+//! # let mut animation = Animation::new(&frames);
+//! # fn millis() -> u32 { 0 }
+//! let mut renderer = Renderer::new(Style::Blocks).live(true);
+//! loop {
+//!     led_matrix.animation_tick(&mut animation, millis()).unwrap();
+//!     renderer.show(&emulator).unwrap();
+//!     thread::sleep(Duration::from_millis(10));
+//! }
+//! ```
+
+use std::format;
+use std::io::{self, Write};
+use std::string::String;
+
+use crate::emulator::Emulator;
+use crate::tiled::locate;
+use crate::Tile;
+
+/// How LEDs are drawn.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Style {
+    /// `#` is "on", `.` is "off".
+    Ascii,
+    /// Two Unicode blocks per LED, so LEDs look square: `██` is "on", `░░` is "off".
+    Blocks,
+}
+
+impl Style {
+    fn pixel(&self, on: bool) -> &'static str {
+        match (self, on) {
+            (Style::Ascii, true) => "#",
+            (Style::Ascii, false) => ".",
+            (Style::Blocks, true) => "██",
+            (Style::Blocks, false) => "░░",
+        }
+    }
+}
+
+/// Text picture of emulated matrices.
+#[derive(Clone, Debug)]
+pub struct Renderer {
+    style: Style,
+    live: bool,
+    /// Lines printed by the previous `show`, live mode moves the cursor back over them.
+    shown_lines: usize,
+}
+
+impl Renderer {
+    pub fn new(style: Style) -> Self {
+        Renderer {
+            style,
+            live: false,
+            shown_lines: 0,
+        }
+    }
+
+    /// Redraw the previous picture in place with ANSI escape codes.
+    pub fn live(self, live: bool) -> Self {
+        Renderer { live, ..self }
+    }
+
+    /// LEDs of the chip and a status line with the intensity and the shutdown state.
+    pub fn render(&self, emulator: &Emulator) -> String {
+        let dimensions = emulator.dimensions();
+        let mut text = String::new();
+        for row_idx in 0..dimensions.rows_count() {
+            for column_idx in 0..dimensions.columns_count() {
+                text += self.style.pixel(emulator.pixel(column_idx, row_idx));
+            }
+            text += "\n";
+        }
+        text += &status(emulator);
+        text += "\n";
+        text
+    }
+
+    /// LEDs of several chips placed like in `TiledDisplay`, `tiles[i]` describes `emulators[i]`.
+    /// Status lines of chips follow the picture.
+    pub fn render_tiles(&self, emulators: &[Emulator], tiles: &[Tile]) -> String {
+        let sizes = emulators.iter().zip(tiles).map(|(emulator, tile)| {
+            let dimensions = emulator.dimensions();
+            let (columns_count, rows_count) = (dimensions.columns_count(), dimensions.rows_count());
+            tile.orientation.size(columns_count, rows_count)
+        });
+        let (cell_width, cell_height) = sizes.fold((0, 0), |(cell_width, cell_height), size| {
+            (cell_width.max(size.0), cell_height.max(size.1))
+        });
+        let grid_columns = tiles.iter().map(|tile| tile.column as u16 + 1).max();
+        let grid_rows = tiles.iter().map(|tile| tile.row as u16 + 1).max();
+        let width = grid_columns.unwrap_or(0) * cell_width as u16;
+        let height = grid_rows.unwrap_or(0) * cell_height as u16;
+
+        let mut text = String::new();
+        for y in 0..height {
+            for x in 0..width {
+                let located = locate(tiles, (cell_width, cell_height), x, y, |idx| {
+                    let dimensions = emulators.get(idx)?.dimensions();
+                    Some((dimensions.columns_count(), dimensions.rows_count()))
+                });
+                let on = located.is_some_and(|(idx, column_idx, row_idx)| {
+                    emulators[idx].pixel(column_idx, row_idx)
+                });
+                text += self.style.pixel(on);
+            }
+            text += "\n";
+        }
+        for (emulator, tile) in emulators.iter().zip(tiles) {
+            text += &format!("[{}, {}] {}\n", tile.column, tile.row, status(emulator));
+        }
+        text
+    }
+
+    /// Print the picture of the chip to stdout.
+    pub fn show(&mut self, emulator: &Emulator) -> io::Result<()> {
+        let text = self.render(emulator);
+        self.print(&text)
+    }
+
+    /// Print the picture of tiles to stdout.
+    pub fn show_tiles(&mut self, emulators: &[Emulator], tiles: &[Tile]) -> io::Result<()> {
+        let text = self.render_tiles(emulators, tiles);
+        self.print(&text)
+    }
+
+    fn print(&mut self, text: &str) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        if self.live && self.shown_lines > 0 {
+            // move the cursor up to the first line of the previous picture and clear below
+            write!(stdout, "\x1b[{}A\x1b[J", self.shown_lines)?;
+        }
+        stdout.write_all(text.as_bytes())?;
+        stdout.flush()?;
+        self.shown_lines = text.lines().count();
+        Ok(())
+    }
+}
+
+/// Address, array mode, intensity and the shutdown state of the chip.
+fn status(emulator: &Emulator) -> String {
+    let intensity = match emulator.intensity() {
+        Some(intensity) => format!("{}mA ({}%)", intensity.milliamps(), intensity.percent()),
+        None => "reserved intensity".into(),
+    };
+    let state = if emulator.is_shutdown() {
+        "shutdown"
+    } else {
+        "on"
+    };
+    format!(
+        "0x{:02x} {:?} {} {}",
+        emulator.address(),
+        emulator.dimensions(),
+        intensity,
+        state
+    )
+}
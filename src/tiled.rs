@@ -0,0 +1,210 @@
+use core::fmt::Debug;
+
+use embedded_hal::i2c::I2c;
+
+#[cfg(feature = "font")]
+use crate::marquee::Marquee;
+use crate::{DriverError, Geometry, MatrixDimensions, Orientation, IS31FL3728};
+
+/// Position and orientation of one matrix in the [`TiledDisplay`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Tile {
+    /// Column of the grid, starts from 0 on the left.
+    pub column: u8,
+    /// Row of the grid, starts from 0 on the top.
+    pub row: u8,
+    pub orientation: Orientation,
+}
+
+impl Tile {
+    pub fn new(column: u8, row: u8, orientation: Orientation) -> Self {
+        Tile {
+            column,
+            row,
+            orientation,
+        }
+    }
+}
+
+/// Index of the tile and the pixel of its matrix which show the point of the grid
+/// with cells of the same size. `size(i)` is the size of the `i`-th matrix
+/// before the orientation of its tile, `None` if there is no matrix.
+pub(crate) fn locate(
+    tiles: &[Tile],
+    (cell_width, cell_height): (u8, u8),
+    x: u16,
+    y: u16,
+    size: impl Fn(usize) -> Option<(u8, u8)>,
+) -> Option<(usize, u8, u8)> {
+    if cell_width == 0 || cell_height == 0 {
+        return None;
+    }
+    let column = x / cell_width as u16;
+    let row = y / cell_height as u16;
+    let cell_x = (x % cell_width as u16) as u8;
+    let cell_y = (y % cell_height as u16) as u8;
+
+    tiles.iter().enumerate().find_map(|(idx, tile)| {
+        if tile.column as u16 != column || tile.row as u16 != row {
+            return None;
+        }
+        let (matrix_width, matrix_height) = size(idx)?;
+        let (matrix_x, matrix_y) =
+            tile.orientation
+                .to_matrix(cell_x, cell_y, matrix_width, matrix_height)?;
+        Some((idx, matrix_x, matrix_y))
+    })
+}
+
+/// Several matrices which work as one display.
+///
+/// Each driver is placed on a grid cell of the same size, `tiles[i]` describes `drivers[i]`.
+/// Drawing changes framebuffers of drivers, `flush` sends changes to all chips
+/// and then updates them back to back, so all tiles change together.
+///
+/// All drivers have the same `I2C` type. Tiles may sit on different buses,
+/// but if the buses have different types (like two peripherals of a HAL),
+/// give drivers `&mut dyn I2c<Error = E>` with a common error type,
+/// for example [`ErrorKind`](embedded_hal::i2c::ErrorKind) returned by adapters of the buses.
+pub struct TiledDisplay<I2C, const N: usize, G: Geometry = MatrixDimensions> {
+    drivers: [IS31FL3728<I2C, G>; N],
+    tiles: [Tile; N],
+    cell_width: u8,
+    cell_height: u8,
+    width: u16,
+    height: u16,
+}
+
+impl<I2C, const N: usize, G: Geometry> TiledDisplay<I2C, N, G> {
+    /// Create display from drivers and their positions.
+    pub fn new(drivers: [IS31FL3728<I2C, G>; N], tiles: [Tile; N]) -> Self {
+        let mut cell_width = 0;
+        let mut cell_height = 0;
+        let mut grid_columns = 0;
+        let mut grid_rows = 0;
+        for (driver, tile) in drivers.iter().zip(tiles.iter()) {
+            let (width, height) = tile.orientation.size(driver.width(), driver.height());
+            cell_width = cell_width.max(width);
+            cell_height = cell_height.max(height);
+            grid_columns = grid_columns.max(tile.column as u16 + 1);
+            grid_rows = grid_rows.max(tile.row as u16 + 1);
+        }
+
+        TiledDisplay {
+            drivers,
+            tiles,
+            cell_width,
+            cell_height,
+            width: grid_columns * cell_width as u16,
+            height: grid_rows * cell_height as u16,
+        }
+    }
+
+    /// Destroy the display and return drivers.
+    pub fn release(self) -> [IS31FL3728<I2C, G>; N] {
+        self.drivers
+    }
+
+    /// Drivers of tiles, for example to change intensity of one tile.
+    pub fn drivers_mut(&mut self) -> &mut [IS31FL3728<I2C, G>; N] {
+        &mut self.drivers
+    }
+
+    /// Width of the whole display in pixels.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Height of the whole display in pixels.
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Index of driver and the pixel of its matrix which show the point of the display.
+    fn locate(&self, x: u16, y: u16) -> Option<(usize, u8, u8)> {
+        locate(
+            &self.tiles,
+            (self.cell_width, self.cell_height),
+            x,
+            y,
+            |idx| {
+                let driver = &self.drivers[idx];
+                Some((driver.width(), driver.height()))
+            },
+        )
+    }
+
+    /// Turn on or off pixel of the display. Use `flush` to show changes.
+    /// Pixels outside of tiles are ignored.
+    pub fn set_pixel(&mut self, x: u16, y: u16, on: bool) {
+        if let Some((idx, matrix_x, matrix_y)) = self.locate(x, y) {
+            self.drivers[idx].set_pixel(matrix_x, matrix_y, on);
+        }
+    }
+
+    /// State of pixel of the display. Pixels outside of tiles are off.
+    pub fn get_pixel(&self, x: u16, y: u16) -> bool {
+        match self.locate(x, y) {
+            Some((idx, matrix_x, matrix_y)) => self.drivers[idx].get_pixel(matrix_x, matrix_y),
+            None => false,
+        }
+    }
+}
+
+impl<I2C, E, const N: usize, G> TiledDisplay<I2C, N, G>
+where
+    I2C: I2c<Error = E>,
+    G: Geometry,
+    E: Debug,
+{
+    /// Send changes to all chips, then update all of them.
+    pub fn flush(&mut self) -> Result<(), DriverError<E>> {
+        for driver in self.drivers.iter_mut() {
+            driver.send_changes()?;
+        }
+        self.update()
+    }
+
+    /// Move the marquee by one column and show it across all tiles.
+    /// Returns `false` if scrolling is finished.
+    #[cfg(feature = "font")]
+    pub fn marquee_step<const M: usize>(
+        &mut self,
+        marquee: &mut Marquee<M>,
+    ) -> Result<bool, DriverError<E>> {
+        if !marquee.advance(self.width) {
+            return Ok(false);
+        }
+        let y = marquee.top(self.height);
+        for x in 0..self.width {
+            let column = marquee.window_column(x, self.width);
+            for row_idx in 0..marquee.height() {
+                self.set_pixel(x, y + row_idx as u16, column & (1 << row_idx) != 0);
+            }
+        }
+        self.flush()?;
+        Ok(true)
+    }
+
+    /// Make a step of the marquee if its step period is passed at `now_ms`.
+    /// Returns `false` if scrolling is finished.
+    #[cfg(feature = "font")]
+    pub fn marquee_tick<const M: usize>(
+        &mut self,
+        marquee: &mut Marquee<M>,
+        now_ms: u32,
+    ) -> Result<bool, DriverError<E>> {
+        if marquee.is_due(now_ms) {
+            self.marquee_step(marquee)?;
+        }
+        Ok(!marquee.is_finished(self.width))
+    }
+
+    /// Update column registers of all chips back to back.
+    pub fn update(&mut self) -> Result<(), DriverError<E>> {
+        for driver in self.drivers.iter_mut() {
+            driver.update()?;
+        }
+        Ok(())
+    }
+}
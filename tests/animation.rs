@@ -0,0 +1,85 @@
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::animation::SpriteSheetError;
+use is31fl3728_rs::{
+    Address, Animation, Frame, MatrixDimensions, PlayMode, SpriteSheet, IS31FL3728,
+};
+
+const ADDRESS: u8 = 0x60;
+
+#[rustfmt::skip]
+const SHEET: [u8; 27] = [
+    b'I', b'S', b'1', 8, 2,
+    100, 0, 0, 0xFF, 0, 0, 0, 0, 0, 0, 0,
+    100, 0, 20, 0, 0xFF, 0, 0, 0, 0, 0, 0,
+];
+
+#[test]
+fn ping_pong_from_sprite_sheet() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x01, 0xFF, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b1011]),
+        Transaction::write(ADDRESS, vec![0x01, 0, 0xFF]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x01, 0xFF, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    let sheet = SpriteSheet::parse(&SHEET).unwrap();
+    let mut animation = Animation::from_sprite_sheet(sheet).mode(PlayMode::PingPong);
+
+    for now_ms in [1000, 1050, 1100, 1150, 1200] {
+        assert!(driver.animation_tick(&mut animation, now_ms).unwrap());
+    }
+
+    mock.done();
+}
+
+#[test]
+fn play_once_stops_on_the_last_frame() {
+    let frames = [
+        Frame::new(&[1], 10),
+        Frame::new(&[2], 20),
+        Frame::new(&[3], 30),
+    ];
+    let mut animation = Animation::new(&frames);
+
+    assert_eq!(animation.poll(0).map(|frame| frame.columns), Some(&[1][..]));
+    assert_eq!(
+        animation.poll(10).map(|frame| frame.columns),
+        Some(&[2][..])
+    );
+    assert_eq!(animation.poll(29), None);
+    assert_eq!(
+        animation.poll(30).map(|frame| frame.columns),
+        Some(&[3][..])
+    );
+    assert!(!animation.is_finished());
+    assert_eq!(animation.poll(60), None);
+    assert!(animation.is_finished());
+}
+
+#[test]
+fn invalid_sprite_sheets() {
+    assert_eq!(
+        SpriteSheet::parse(b"GIF89a"),
+        Err(SpriteSheetError::InvalidHeader)
+    );
+    assert_eq!(
+        SpriteSheet::parse(&SHEET[..26]),
+        Err(SpriteSheetError::InvalidLength)
+    );
+    assert_eq!(
+        SpriteSheet::parse(&[b'I', b'S', b'1', 12, 0]),
+        Err(SpriteSheetError::InvalidColumnsCount(12))
+    );
+    let mut sheet = SHEET;
+    sheet[18] = 21;
+    assert_eq!(
+        SpriteSheet::parse(&sheet),
+        Err(SpriteSheetError::InvalidIntensity(21))
+    );
+}
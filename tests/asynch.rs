@@ -0,0 +1,209 @@
+#![cfg(feature = "async")]
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::{
+    Address, IS31FL3728Async, LightingIntensity, MatrixDimensions, WriteMode, IS31FL3728,
+};
+
+const ADDRESS: u8 = 0x60;
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+/// The mock never waits, so the future is ready at the first poll.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+/// Both drivers have to send the same transactions.
+fn check_both(expectations: &[Transaction]) -> (Mock, Mock) {
+    (Mock::new(expectations), Mock::new(expectations))
+}
+
+#[test]
+fn new_configures_chip() {
+    let (mut mock, mut mock_async) =
+        check_both(&[Transaction::write(ADDRESS, vec![0x00, 0b0000_0101])]);
+
+    IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M7x9, true).unwrap();
+    block_on(IS31FL3728Async::new(
+        mock_async.clone(),
+        Address::Gnd,
+        MatrixDimensions::M7x9,
+        true,
+    ))
+    .unwrap();
+
+    mock.done();
+    mock_async.done();
+}
+
+#[test]
+fn draw_and_flush_in_burst() {
+    let (mut mock, mut mock_async) = check_both(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x01, 1, 2, 3, 0, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x02, 0b1000_0010]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    driver.draw(&[1, 2, 3]).unwrap();
+    driver.set_pixel(1, 1, false);
+    driver.set_pixel(1, 0, true);
+    driver.flush().unwrap();
+
+    block_on(async {
+        let mut driver = IS31FL3728Async::new(
+            mock_async.clone(),
+            Address::Gnd,
+            MatrixDimensions::M8x8,
+            false,
+        )
+        .await
+        .unwrap();
+        driver.draw(&[1, 2, 3]).await.unwrap();
+        driver.set_pixel(1, 1, false);
+        driver.set_pixel(1, 0, true);
+        driver.flush().await.unwrap();
+    });
+
+    mock.done();
+    mock_async.done();
+}
+
+#[test]
+fn flush_per_column() {
+    let mut expectations = vec![Transaction::write(ADDRESS, vec![0x00, 0b11])];
+    for column_number in 1..=11 {
+        expectations.push(Transaction::write(ADDRESS, vec![column_number, 0]));
+    }
+    expectations.extend([
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x02, 0b0000_1000]),
+        Transaction::write(ADDRESS, vec![0x09, 0b0001_0000]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+    let (mut mock, mut mock_async) = check_both(&expectations);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    driver.set_write_mode(WriteMode::PerColumn);
+    driver.clear().unwrap();
+    driver.set_pixel(1, 1, true);
+    driver.set_pixel(8, 0, true);
+    driver.flush().unwrap();
+
+    block_on(async {
+        let mut driver = IS31FL3728Async::new(
+            mock_async.clone(),
+            Address::Gnd,
+            MatrixDimensions::M5x11,
+            false,
+        )
+        .await
+        .unwrap();
+        driver.set_write_mode(WriteMode::PerColumn);
+        driver.clear().await.unwrap();
+        driver.set_pixel(1, 1, true);
+        driver.set_pixel(8, 0, true);
+        driver.flush().await.unwrap();
+    });
+
+    mock.done();
+    mock_async.done();
+}
+
+#[test]
+fn set_intensity() {
+    let (mut mock, mut mock_async) = check_both(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b10]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1011]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M6x10, false).unwrap();
+    driver.set_intensity(LightingIntensity::C20mA).unwrap();
+    assert_eq!(driver.intensity(), LightingIntensity::C20mA);
+
+    block_on(async {
+        let mut driver = IS31FL3728Async::new(
+            mock_async.clone(),
+            Address::Gnd,
+            MatrixDimensions::M6x10,
+            false,
+        )
+        .await
+        .unwrap();
+        driver
+            .set_intensity(LightingIntensity::C20mA)
+            .await
+            .unwrap();
+        assert_eq!(driver.intensity(), LightingIntensity::C20mA);
+    });
+
+    mock.done();
+    mock_async.done();
+}
+
+#[test]
+fn resync_rewrites_registers_and_frame() {
+    let (mut mock, mut mock_async) = check_both(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b01]),
+        Transaction::write(ADDRESS, vec![0x01, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1001]),
+        // resync
+        Transaction::write(ADDRESS, vec![0x00, 0b01]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1001]),
+        Transaction::write(ADDRESS, vec![0x0F, 0]),
+        Transaction::write(ADDRESS, vec![0x01, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x01, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M7x9, false).unwrap();
+    driver.draw(&[1]).unwrap();
+    driver.set_intensity(LightingIntensity::C10mA).unwrap();
+    driver.resync().unwrap();
+    let registers = driver.registers().clone();
+
+    block_on(async {
+        let mut driver = IS31FL3728Async::new(
+            mock_async.clone(),
+            Address::Gnd,
+            MatrixDimensions::M7x9,
+            false,
+        )
+        .await
+        .unwrap();
+        driver.draw(&[1]).await.unwrap();
+        driver
+            .set_intensity(LightingIntensity::C10mA)
+            .await
+            .unwrap();
+        driver.resync().await.unwrap();
+        assert_eq!(driver.registers(), &registers);
+    });
+
+    mock.done();
+    mock_async.done();
+}
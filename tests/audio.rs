@@ -0,0 +1,91 @@
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::{
+    Address, AudioInputGain, AudioMode, DisplayMode, LightingIntensity, MatrixDimensions,
+    IS31FL3728,
+};
+
+const ADDRESS: u8 = 0x60;
+
+#[test]
+fn minus_6db_gain_is_in_gain_bits() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0111_0000]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    driver
+        .set_audio_input_gain(AudioInputGain::GMinus6dB)
+        .unwrap();
+    assert_eq!(driver.audio_mode().gain, AudioInputGain::GMinus6dB);
+
+    mock.done();
+}
+
+#[test]
+fn audio_mode_sends_only_changed_registers() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b01]),
+        Transaction::write(ADDRESS, vec![0x00, 0b0000_0101]),
+        Transaction::write(ADDRESS, vec![0x0F, 0b0100_0000]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0011_0000]),
+        // back to the matrix, gain is kept
+        Transaction::write(ADDRESS, vec![0x0F, 0b0000_0000]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M7x9, false).unwrap();
+    assert_eq!(driver.audio_mode(), AudioMode::OFF);
+
+    let equalizer = AudioMode::EQUALIZER.with_gain(AudioInputGain::G09dB);
+    driver.set_audio_mode(equalizer).unwrap();
+    driver.set_audio_mode(equalizer).unwrap();
+    assert_eq!(driver.audio_mode(), equalizer);
+
+    driver.set_display_mode(DisplayMode::Matrix).unwrap();
+    assert_eq!(
+        driver.audio_mode(),
+        AudioMode::MODULATED.with_gain(AudioInputGain::G09dB)
+    );
+
+    mock.done();
+}
+
+#[test]
+fn audio_input_keeps_shutdown_and_array_mode() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b0000_0110]),
+        Transaction::write(ADDRESS, vec![0x00, 0b1000_0110]),
+        Transaction::write(ADDRESS, vec![0x00, 0b1000_0010]),
+        Transaction::write(ADDRESS, vec![0x00, 0b0000_0010]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M6x10, true).unwrap();
+    driver.software_shutdown().unwrap();
+    driver.set_audio_input(false).unwrap();
+    driver.software_on().unwrap();
+    assert!(!driver.audio_mode().input_enabled);
+
+    mock.done();
+}
+
+#[test]
+fn gain_and_intensity_share_the_register() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0110_0000]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0110_1000]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    driver
+        .set_audio_mode(AudioMode::OFF.with_gain(AudioInputGain::G18dB))
+        .unwrap();
+    driver.set_intensity(LightingIntensity::C05mA).unwrap();
+    assert_eq!(driver.audio_mode().gain, AudioInputGain::G18dB);
+
+    mock.done();
+}
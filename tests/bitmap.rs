@@ -0,0 +1,134 @@
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::geometry;
+use is31fl3728_rs::{Address, DriverError, MatrixDimensions, IS31FL3728};
+
+const ADDRESS: u8 = 0x60;
+
+/// Configuration, one burst of columns and update.
+fn expectations(mode: u8, columns: &[u8]) -> Vec<Transaction> {
+    let mut burst = vec![0x01];
+    burst.extend_from_slice(columns);
+    vec![
+        Transaction::write(ADDRESS, vec![0x00, mode]),
+        Transaction::write(ADDRESS, burst),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]
+}
+
+#[test]
+fn bitmap_8x8() {
+    let mut mock = Mock::new(&expectations(
+        0b00,
+        &[
+            0b0011_1000,
+            0b0111_1100,
+            0b0111_1110,
+            0b0011_1111,
+            0b0011_1111,
+            0b0111_1110,
+            0b0111_1100,
+            0b0011_1000,
+        ],
+    ));
+
+    let mut driver = IS31FL3728::new(mock.clone(), Address::Gnd, geometry::M8x8, false).unwrap();
+    driver
+        .draw_bitmap(&[
+            0b00000000, 0b01100110, 0b11111111, 0b11111111, 0b11111111, 0b01111110, 0b00111100,
+            0b00011000,
+        ])
+        .unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn bitmap_7x9() {
+    let mut mock = Mock::new(&expectations(
+        0b01,
+        &[
+            0b0100_0001,
+            0b0010_0001,
+            0b0001_0001,
+            0b0000_1001,
+            0b0000_0101,
+            0b0000_0011,
+            0b0000_0001,
+            0b0000_0001,
+            0b0100_0001,
+        ],
+    ));
+
+    let mut driver = IS31FL3728::new(mock.clone(), Address::Gnd, geometry::M7x9, false).unwrap();
+    driver
+        .draw_bitmap(&[
+            0b1_0000_0001,
+            0b0_1000_0000,
+            0b0_0100_0000,
+            0b0_0010_0000,
+            0b0_0001_0000,
+            0b0_0000_1000,
+            0b1_1111_1111,
+        ])
+        .unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn bitmap_6x10() {
+    let mut mock = Mock::new(&expectations(
+        0b10,
+        &[0x22, 0x21, 0x02, 0x01, 0x0A, 0x09, 0x02, 0x01, 0x02, 0x11],
+    ));
+
+    let mut driver = IS31FL3728::new(mock.clone(), Address::Gnd, geometry::M6x10, false).unwrap();
+    driver
+        .draw_bitmap(&[
+            0b11_0000_0000,
+            0b00_0000_0001,
+            0b00_0011_0000,
+            0b00_0000_0000,
+            0b10_1010_1010,
+            0b01_0101_0101,
+        ])
+        .unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn bitmap_5x11() {
+    let mut mock = Mock::new(&expectations(
+        0b11,
+        &[0x1F, 0x11, 0x1F, 0, 0, 0, 0x01, 0x02, 0x04, 0x08, 0x10],
+    ));
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    let picture: [u16; 5] = [
+        0b111_0000_0001,
+        0b101_0000_0010,
+        0b101_0000_0100,
+        0b101_0000_1000,
+        0b111_0001_0000,
+    ];
+    driver.draw_bitmap(&picture).unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn bitmap_with_wrong_rows_count() {
+    let mut mock = Mock::new(&[Transaction::write(ADDRESS, vec![0x00, 0b11])]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    let result = driver.draw_bitmap(&[0u8; 8]);
+    assert!(matches!(
+        result,
+        Err(DriverError::FrameLengthMismatch(8, 5))
+    ));
+
+    mock.done();
+}
@@ -0,0 +1,116 @@
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::geometry::{self, StaticGeometry};
+use is31fl3728_rs::{Address, DriverError, MatrixDimensions, UnknownAddress, IS31FL3728};
+
+#[test]
+fn address_from_ad_pin() {
+    assert_eq!(Address::try_from(0x60), Ok(Address::Gnd));
+    assert_eq!(Address::try_from(0x63), Ok(Address::Vcc));
+    assert_eq!(Address::Sda.value(), 0x62);
+}
+
+#[test]
+fn other_addresses_need_custom() {
+    assert_eq!(Address::try_from(0x64), Err(UnknownAddress(0x64)));
+    assert_eq!(Address::try_from(0x5F), Err(UnknownAddress(0x5F)));
+    assert_eq!(Address::Custom(0x64).value(), 0x64);
+}
+
+#[test]
+fn reserved_address_is_rejected() {
+    // no transactions are expected, the driver must fail before using the bus
+    let mut mock = Mock::new(&[]);
+
+    let result = IS31FL3728::new(
+        mock.clone(),
+        Address::Custom(0x7C),
+        MatrixDimensions::M8x8,
+        false,
+    );
+    assert!(matches!(result, Err(DriverError::InvalidAddress(0x7C))));
+
+    mock.done();
+}
+
+#[test]
+fn driver_uses_ad_pin_address() {
+    let mut mock = Mock::new(&[Transaction::write(0x61, vec![0x00, 0b00])]);
+
+    let driver = IS31FL3728::new(mock.clone(), Address::Scl, MatrixDimensions::M8x8, false);
+    assert!(driver.is_ok());
+
+    mock.done();
+}
+
+#[test]
+fn static_geometry_draws_typed_frame() {
+    let mut mock = Mock::new(&[
+        Transaction::write(0x60, vec![0x00, 0b11]),
+        Transaction::write(0x60, vec![0x01, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]),
+        Transaction::write(0x60, vec![0x0C, 0]),
+    ]);
+
+    let mut driver = IS31FL3728::new(mock.clone(), Address::Gnd, geometry::M5x11, false).unwrap();
+    assert_eq!(
+        driver.columns_count(),
+        <geometry::M5x11 as StaticGeometry>::COLUMNS
+    );
+    driver.draw(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]).unwrap();
+
+    mock.done();
+}
+
+const NACK: ErrorKind = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+
+#[test]
+fn probe_answering_and_silent_addresses() {
+    let mut mock = Mock::new(&[
+        Transaction::write(0x60, vec![]),
+        Transaction::write(0x61, vec![]).with_error(NACK),
+    ]);
+
+    let mut i2c = mock.clone();
+    assert_eq!(IS31FL3728::<_>::probe(&mut i2c, Address::Gnd), Ok(()));
+    assert_eq!(
+        IS31FL3728::<_>::probe(&mut i2c, Address::Scl),
+        Err(DriverError::I2C(NACK))
+    );
+
+    mock.done();
+}
+
+#[test]
+fn try_new_returns_error_of_probe() {
+    // the chip isn't configured when it doesn't answer
+    let mut mock = Mock::new(&[Transaction::write(0x62, vec![]).with_error(NACK)]);
+
+    let result = IS31FL3728::try_new(mock.clone(), Address::Sda, MatrixDimensions::M8x8, false);
+    assert!(matches!(result, Err(DriverError::I2C(NACK))));
+
+    mock.done();
+}
+
+#[test]
+fn try_new_configures_answering_chip() {
+    let mut mock = Mock::new(&[
+        Transaction::write(0x63, vec![]),
+        Transaction::write(0x63, vec![0x00, 0b0000_0101]),
+    ]);
+
+    let result = IS31FL3728::try_new(mock.clone(), Address::Vcc, MatrixDimensions::M7x9, true);
+    assert!(result.is_ok());
+
+    mock.done();
+}
+
+#[test]
+fn new_returns_error_of_configuration() {
+    let mut mock =
+        Mock::new(&[Transaction::write(0x60, vec![0x00, 0b10]).with_error(ErrorKind::Bus)]);
+
+    let result = IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M6x10, false);
+    assert!(matches!(result, Err(DriverError::I2C(ErrorKind::Bus))));
+
+    mock.done();
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "emulator")]
+
+use is31fl3728_rs::emulator::{Emulator, EmulatorError};
+use is31fl3728_rs::{
+    Address, AudioInputGain, AudioMode, DriverError, LightingIntensity, MatrixDimensions,
+    RegisterShadow, IS31FL3728,
+};
+
+const ADDRESS: u8 = 0x60;
+
+/// The shadow of the driver matches registers decoded by the emulator.
+fn assert_same_registers(registers: &RegisterShadow, emulator: &Emulator) {
+    for address in 0x00..=0x0F {
+        assert_eq!(registers.register(address), emulator.register(address));
+    }
+    assert_eq!(registers.latched_columns(), &emulator.displayed_columns());
+}
+
+#[test]
+fn columns_are_shown_after_update() {
+    let emulator = Emulator::new(ADDRESS);
+    let mut driver = IS31FL3728::new(
+        emulator.clone(),
+        Address::Gnd,
+        MatrixDimensions::M5x11,
+        false,
+    )
+    .unwrap();
+    assert_eq!(emulator.dimensions(), MatrixDimensions::M5x11);
+
+    driver.set_pixel(0, 0, true);
+    driver.set_pixel(10, 4, true);
+    driver.send_changes().unwrap();
+    assert_eq!(emulator.temporary_columns()[0], 0b0001_0000);
+    assert_eq!(emulator.temporary_columns()[10], 0b0000_0001);
+    assert!(!emulator.pixel(0, 0));
+
+    driver.update().unwrap();
+    assert_eq!(emulator.updates(), 1);
+    assert!(emulator.pixel(0, 0));
+    assert!(emulator.pixel(10, 4));
+    let pixels = emulator.pixels();
+    assert_eq!((pixels.len(), pixels[0].len()), (5, 11));
+    assert_eq!(pixels.iter().flatten().filter(|on| **on).count(), 2);
+}
+
+#[test]
+fn registers_of_configuration() {
+    let emulator = Emulator::new(ADDRESS);
+    let mut driver =
+        IS31FL3728::new(emulator.clone(), Address::Gnd, MatrixDimensions::M8x8, true).unwrap();
+    assert!(emulator.is_audio_input_enabled());
+    assert_eq!(emulator.intensity(), Some(LightingIntensity::C40mA));
+
+    driver.set_intensity(LightingIntensity::C05mA).unwrap();
+    assert_eq!(emulator.intensity(), Some(LightingIntensity::C05mA));
+
+    driver.audio_eq_enable().unwrap();
+    assert!(emulator.is_audio_eq_enabled());
+    driver
+        .set_audio_mode(AudioMode::MODULATED.with_gain(AudioInputGain::G12dB))
+        .unwrap();
+    assert!(!emulator.is_audio_eq_enabled());
+    assert_eq!(emulator.audio_input_gain(), AudioInputGain::G12dB);
+    assert_eq!(emulator.intensity(), Some(LightingIntensity::C05mA));
+
+    driver.fill().unwrap();
+    assert!(emulator.pixel(7, 7));
+    driver.software_shutdown().unwrap();
+    assert!(emulator.is_shutdown());
+    assert_same_registers(driver.registers(), &emulator);
+    assert!(!emulator.pixel(7, 7));
+}
+
+#[test]
+fn other_address_is_not_acknowledged() {
+    let emulator = Emulator::new(ADDRESS);
+    let result = IS31FL3728::new(emulator, Address::Scl, MatrixDimensions::M8x8, false);
+    assert!(matches!(
+        result,
+        Err(DriverError::I2C(EmulatorError::NoAcknowledge(0x61)))
+    ));
+}
+
+#[test]
+fn resync_after_power_cycle() {
+    let emulator = Emulator::new(ADDRESS);
+    let mut driver = IS31FL3728::new(
+        emulator.clone(),
+        Address::Gnd,
+        MatrixDimensions::M6x10,
+        true,
+    )
+    .unwrap();
+    driver.set_intensity(LightingIntensity::C15mA).unwrap();
+    driver.draw(&[0b1010_0000; 10]).unwrap();
+    driver.send_column(1, 0).unwrap();
+
+    emulator.reset();
+    assert_eq!(emulator.dimensions(), MatrixDimensions::M8x8);
+    driver.resync().unwrap();
+    assert_same_registers(driver.registers(), &emulator);
+    assert!(emulator.pixel(0, 0));
+    assert_eq!(emulator.temporary_columns()[0], 0);
+
+    driver.reset().unwrap();
+    assert_same_registers(driver.registers(), &emulator);
+    assert_eq!(emulator.dimensions(), MatrixDimensions::M6x10);
+    assert!(!emulator.pixel(9, 0));
+}
+
+#[test]
+fn equalizer_hides_matrix() {
+    let emulator = Emulator::new(ADDRESS);
+    let mut driver =
+        IS31FL3728::new(emulator.clone(), Address::Gnd, MatrixDimensions::M8x8, true).unwrap();
+    driver.fill().unwrap();
+    assert!(emulator.pixel(0, 0));
+
+    driver.set_audio_mode(AudioMode::EQUALIZER).unwrap();
+    assert!(emulator.is_audio_eq_enabled());
+    assert!(!emulator.pixel(0, 0));
+    assert!(emulator.pixels().iter().flatten().all(|on| !on));
+
+    driver.set_audio_mode(AudioMode::MODULATED).unwrap();
+    assert!(emulator.pixel(0, 0));
+}
@@ -0,0 +1,114 @@
+use embedded_hal::i2c::{Error, ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::{Address, DriverError, MatrixDimensions, IS31FL3728};
+
+const ADDRESS: u8 = 0x60;
+
+#[test]
+fn column_0_is_rejected() {
+    let mut mock = Mock::new(&[Transaction::write(ADDRESS, vec![0x00, 0b00])]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    let result = driver.send_column(0, 0xFF);
+    assert_eq!(result, Err(DriverError::ColumnOutOfRange(0, 8)));
+    let result = driver.draw_column(0, 0xFF);
+    assert_eq!(result, Err(DriverError::ColumnOutOfRange(0, 8)));
+    let result = driver.write_columns(0, &[0xFF]);
+    assert_eq!(result, Err(DriverError::ColumnOutOfRange(0, 8)));
+
+    mock.done();
+}
+
+#[test]
+fn columns_out_of_range() {
+    let mut mock = Mock::new(&[Transaction::write(ADDRESS, vec![0x00, 0b01])]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M7x9, false).unwrap();
+    let result = driver.send_column(10, 0xFF);
+    assert_eq!(result, Err(DriverError::ColumnOutOfRange(10, 9)));
+    let result = driver.write_columns(8, &[1, 2, 3]);
+    assert_eq!(result, Err(DriverError::ColumnOutOfRange(10, 9)));
+    let result = driver.write_columns(1, &[0; 300]);
+    assert_eq!(result, Err(DriverError::ColumnOutOfRange(255, 9)));
+
+    mock.done();
+}
+
+#[test]
+fn too_long_frame() {
+    let mut mock = Mock::new(&[Transaction::write(ADDRESS, vec![0x00, 0b00])]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    let result = driver.draw(&[0; 9]);
+    assert_eq!(result, Err(DriverError::FrameLengthMismatch(9, 8)));
+
+    mock.done();
+}
+
+#[test]
+fn display_messages() {
+    let error: DriverError<ErrorKind> = DriverError::ColumnOutOfRange(0, 8);
+    assert_eq!(error.to_string(), "column 0 is out of range 1..=8");
+    let error: DriverError<ErrorKind> = DriverError::FrameLengthMismatch(8, 5);
+    assert_eq!(
+        error.to_string(),
+        "frame of length 8 doesn't fit the matrix of size 5"
+    );
+    let error: DriverError<ErrorKind> = DriverError::InvalidAddress(0x7C);
+    assert_eq!(error.to_string(), "invalid I2C address 0x7c");
+    let error = DriverError::I2C(ErrorKind::Bus);
+    assert_eq!(error.to_string(), "I2C error: Bus");
+    let error: DriverError<ErrorKind> = DriverError::BitmapRowTooNarrow(8, 9);
+    assert_eq!(
+        error.to_string(),
+        "bitmap row of 8 pixels doesn't hold the matrix of width 9"
+    );
+}
+
+#[test]
+fn too_narrow_bitmap_rows() {
+    let mut mock = Mock::new(&[Transaction::write(ADDRESS, vec![0x00, 0b01])]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M7x9, false).unwrap();
+    let result = driver.draw_bitmap(&[0u8; 7]);
+    assert_eq!(result, Err(DriverError::BitmapRowTooNarrow(8, 9)));
+
+    mock.done();
+}
+
+#[test]
+fn kind_of_errors() {
+    let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+    assert_eq!(DriverError::I2C(nack).kind(), nack);
+    assert_eq!(
+        DriverError::<ErrorKind>::InvalidPixelMap.kind(),
+        ErrorKind::Other
+    );
+}
+
+fn question_mark(result: Result<(), ErrorKind>) -> Result<(), DriverError<ErrorKind>> {
+    result?;
+    Ok(())
+}
+
+fn boxed(error: DriverError<ErrorKind>) -> Result<(), Box<dyn std::error::Error>> {
+    Err(error)?;
+    Ok(())
+}
+
+#[test]
+fn errors_are_converted_by_question_mark() {
+    assert_eq!(
+        question_mark(Err(ErrorKind::Overrun)),
+        Err(DriverError::I2C(ErrorKind::Overrun))
+    );
+    let error = boxed(DriverError::UnsupportedMode).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "operation isn't supported by the orientation"
+    );
+}
@@ -0,0 +1,95 @@
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::{Address, Fade, LightingIntensity, MatrixDimensions, Ramp, IS31FL3728};
+
+const ADDRESS: u8 = 0x60;
+
+#[test]
+fn intensity_conversions() {
+    assert_eq!(
+        LightingIntensity::C75mA.brighter(),
+        LightingIntensity::C75mA
+    );
+    assert_eq!(
+        LightingIntensity::C35mA.brighter(),
+        LightingIntensity::C40mA
+    );
+    assert_eq!(LightingIntensity::C05mA.dimmer(), LightingIntensity::C05mA);
+    assert_eq!(LightingIntensity::C40mA.dimmer(), LightingIntensity::C35mA);
+
+    assert_eq!(LightingIntensity::C35mA.milliamps(), 35);
+    assert_eq!(
+        LightingIntensity::from_milliamps(0),
+        LightingIntensity::C05mA
+    );
+    assert_eq!(
+        LightingIntensity::from_milliamps(43),
+        LightingIntensity::C45mA
+    );
+    assert_eq!(
+        LightingIntensity::from_milliamps(255),
+        LightingIntensity::C75mA
+    );
+
+    assert_eq!(LightingIntensity::C75mA.percent(), 100);
+    assert_eq!(LightingIntensity::C05mA.percent(), 7);
+    assert_eq!(
+        LightingIntensity::from_percent(50),
+        LightingIntensity::C40mA
+    );
+    assert_eq!(
+        LightingIntensity::from_percent(200),
+        LightingIntensity::C75mA
+    );
+}
+
+#[test]
+fn gamma_ramp_is_slower_at_low_intensity() {
+    let linear = Fade::new(LightingIntensity::C05mA, LightingIntensity::C75mA, 100);
+    let gamma = linear.ramp(Ramp::Gamma);
+
+    assert_eq!(linear.intensity_at(50), LightingIntensity::C40mA);
+    assert_eq!(gamma.intensity_at(50), LightingIntensity::C30mA);
+    assert_eq!(gamma.intensity_at(100), LightingIntensity::C75mA);
+}
+
+#[test]
+fn fade_out_through_shutdown() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b1100]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b1000]),
+        Transaction::write(ADDRESS, vec![0x00, 0b1000_0000]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    let mut fade =
+        Fade::new(LightingIntensity::C40mA, LightingIntensity::C05mA, 20).through_shutdown(true);
+
+    assert!(driver.fade_tick(&mut fade, 1000).unwrap());
+    assert!(driver.fade_tick(&mut fade, 1010).unwrap());
+    assert!(!driver.fade_tick(&mut fade, 1020).unwrap());
+    assert!(!driver.fade_tick(&mut fade, 1030).unwrap());
+
+    mock.done();
+}
+
+#[test]
+fn blocking_fade() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b1000]),
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b1001]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b1010]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    let fade =
+        Fade::new(LightingIntensity::C05mA, LightingIntensity::C15mA, 10).through_shutdown(true);
+    driver.fade(fade, &mut NoopDelay::new()).unwrap();
+
+    mock.done();
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "font")]
+
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::font::{FONT_3X5, FONT_5X7};
+use is31fl3728_rs::{Address, MatrixDimensions, IS31FL3728};
+
+const ADDRESS: u8 = 0x60;
+
+#[test]
+fn char_with_font_5x7() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x01, 0, 0x42, 0xFE, 0x02, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    assert_eq!(driver.font().height, 7);
+    let font = driver.font();
+    driver.draw_char(0, 0, '1', font).unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn text_with_font_3x5() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b11]),
+        Transaction::write(
+            ADDRESS,
+            vec![0x01, 0, 0x1F, 0x04, 0x1F, 0, 0x11, 0x1F, 0x11, 0, 0, 0],
+        ),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    assert_eq!(driver.font().height, 5);
+    driver.draw_text(1, 0, "hi", &FONT_3X5).unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn text_width() {
+    assert_eq!(FONT_3X5.text_width("12:30"), 19);
+    assert_eq!(FONT_5X7.text_width(""), 0);
+    assert_eq!(FONT_5X7.glyph('\u{e9}'), FONT_5X7.glyph('?'));
+}
@@ -0,0 +1,102 @@
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::{Address, MatrixDimensions, WriteMode, IS31FL3728};
+
+const ADDRESS: u8 = 0x60;
+
+/// Configuration of 5x11 and the first flush, which sends all columns one by one.
+fn cleared_5x11_per_column() -> Vec<Transaction> {
+    let mut expectations = vec![Transaction::write(ADDRESS, vec![0x00, 0b11])];
+    for column_number in 1..=11 {
+        expectations.push(Transaction::write(ADDRESS, vec![column_number, 0]));
+    }
+    expectations.push(Transaction::write(ADDRESS, vec![0x0C, 0]));
+    expectations
+}
+
+#[test]
+fn per_column_flush_sends_only_dirty_columns() {
+    let mut expectations = cleared_5x11_per_column();
+    expectations.extend([
+        Transaction::write(ADDRESS, vec![0x01, 0b0001_0000]),
+        Transaction::write(ADDRESS, vec![0x0B, 0b0000_0001]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+    let mut mock = Mock::new(&expectations);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    driver.set_write_mode(WriteMode::PerColumn);
+    driver.clear().unwrap();
+
+    driver.set_pixel(0, 0, true);
+    driver.set_pixel(10, 4, true);
+    driver.flush().unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn per_column_explicit_writes_send_unchanged_columns() {
+    let mut expectations = cleared_5x11_per_column();
+    expectations.extend([
+        Transaction::write(ADDRESS, vec![0x03, 0]),
+        Transaction::write(ADDRESS, vec![0x05, 0]),
+        Transaction::write(ADDRESS, vec![0x06, 0]),
+    ]);
+    let mut mock = Mock::new(&expectations);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    driver.set_write_mode(WriteMode::PerColumn);
+    driver.clear().unwrap();
+
+    driver.send_column(3, 0).unwrap();
+    driver.write_columns(5, &[0, 0]).unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn second_flush_without_changes_sends_only_update() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x01, 1, 2, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    driver.draw(&[1, 2]).unwrap();
+    driver.flush().unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn set_and_toggle_pixel_resend_only_its_column() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x04, 0b0010_0000]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x07, 0b0000_0001]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        // the pixel is set again, nothing is changed
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    driver.clear().unwrap();
+
+    driver.set_pixel(3, 2, true);
+    driver.flush().unwrap();
+    driver.toggle_pixel(6, 7);
+    driver.flush().unwrap();
+    driver.set_pixel(3, 2, true);
+    driver.flush().unwrap();
+
+    mock.done();
+}
@@ -0,0 +1,62 @@
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::grayscale::GrayscaleTiming;
+use is31fl3728_rs::{Address, GrayscaleDisplay, MatrixDimensions, IS31FL3728};
+
+const ADDRESS: u8 = 0x60;
+
+#[test]
+fn bit_planes_are_shown_by_ticks() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        // plane 0 is shown one tick
+        Transaction::write(ADDRESS, vec![0x01, 0x80, 0, 0x80, 0, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        // plane 1 is shown two ticks
+        Transaction::write(ADDRESS, vec![0x02, 0x80, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        // plane 0 again
+        Transaction::write(ADDRESS, vec![0x02, 0, 0x80]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+
+    let driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    let mut display: GrayscaleDisplay<_, _, 2> = GrayscaleDisplay::new(driver);
+    display.set_level(0, 0, 3);
+    display.set_level(1, 0, 2);
+    display.set_level(2, 0, 1);
+    display.set_level(3, 0, 0);
+
+    for _ in 0..4 {
+        display.tick().unwrap();
+    }
+
+    mock.done();
+}
+
+#[test]
+fn timing_depends_on_bus_frequency() {
+    let mut mock = Mock::new(&[Transaction::write(ADDRESS, vec![0x00, 0b00])]);
+
+    let driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    let display: GrayscaleDisplay<_, _, 4> = GrayscaleDisplay::new(driver);
+
+    // 121 clocks per plane at 400 kHz, 15 ticks per frame
+    let timing = display.timing(400_000).unwrap();
+    assert_eq!(
+        timing,
+        GrayscaleTiming {
+            min_tick_period_us: 303,
+            max_refresh_rate_hz: 220,
+        }
+    );
+    assert!(timing.is_flicker_free());
+    assert!(!display.timing(10_000).unwrap().is_flicker_free());
+    assert_eq!(display.timing(0), None);
+    let timing = display.timing(1).unwrap();
+    assert_eq!(timing.min_tick_period_us, 121_000_000);
+    assert_eq!(timing.max_refresh_rate_hz, 0);
+
+    mock.done();
+}
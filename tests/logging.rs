@@ -0,0 +1,59 @@
+#![cfg(all(feature = "rtt-debug", debug_assertions))]
+
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::{Address, LightingIntensity, MatrixDimensions, IS31FL3728};
+use rtt_target::rtt::RttChannel;
+use rtt_target::{set_print_channel, ChannelMode, UpChannel};
+
+const ADDRESS: u8 = 0x60;
+const LOG_SIZE: usize = 1024;
+
+/// Print channel of RTT on a buffer of the test instead of the memory read by a debug probe.
+fn capture_rtt() -> *const [u8; LOG_SIZE] {
+    let buffer: *mut [u8; LOG_SIZE] = Box::leak(Box::new([0; LOG_SIZE]));
+    // all zeros is the state of the channel before `init`, like in `rtt_init!`
+    let channel: &'static mut RttChannel = Box::leak(Box::new(unsafe { std::mem::zeroed() }));
+    unsafe {
+        channel.init(c"log".as_ptr().cast(), ChannelMode::NoBlockSkip, buffer);
+        set_print_channel(UpChannel::new(channel));
+    }
+    buffer
+}
+
+fn read_log(buffer: *const [u8; LOG_SIZE]) -> String {
+    let buffer = unsafe { &*buffer };
+    let len = buffer
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(LOG_SIZE);
+    String::from_utf8_lossy(&buffer[..len]).into_owned()
+}
+
+// the print channel is global, so the only test of the file
+#[test]
+fn rtt_log_of_register_writes() {
+    let log = capture_rtt();
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x02, 0b1010]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b1001]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    driver.send_column(2, 0b1010).unwrap();
+    driver.update().unwrap();
+    driver.set_intensity(LightingIntensity::C10mA).unwrap();
+
+    // values are logged, not names of variables
+    assert_eq!(
+        read_log(log),
+        "IS31FL3728[0x60]: register 0x00 <- [00]\n\
+         IS31FL3728[0x60]: register 0x02 <- [0a]\n\
+         IS31FL3728[0x60]: register 0x0c <- [00]\n\
+         IS31FL3728[0x60]: register 0x0d <- [09]\n"
+    );
+
+    mock.done();
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "macros")]
+
+use is31fl3728_rs::matrix;
+
+#[test]
+fn heart_8x8() {
+    let heart: [u8; 8] = matrix!(
+        M8x8, "........", ".##..##.", "########", "########", "########", ".######.", "..####..",
+        "...##...",
+    );
+    assert_eq!(
+        heart,
+        [
+            0b0011_1000,
+            0b0111_1100,
+            0b0111_1110,
+            0b0011_1111,
+            0b0011_1111,
+            0b0111_1110,
+            0b0111_1100,
+            0b0011_1000,
+        ]
+    );
+}
+
+#[test]
+fn picture_5x11() {
+    const PICTURE: [u8; 11] = matrix!(
+        is31fl3728_rs::MatrixDimensions::M5x11,
+        "###_______X",
+        "#_#______X_",
+        "#_#_____X__",
+        "#_#____X___",
+        "###___X____"
+    );
+    assert_eq!(
+        PICTURE,
+        [0x1F, 0x11, 0x1F, 0, 0, 0, 0x01, 0x02, 0x04, 0x08, 0x10]
+    );
+}
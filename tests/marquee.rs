@@ -0,0 +1,63 @@
+#![cfg(feature = "font")]
+
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::font::{FONT_3X5, FONT_5X7};
+use is31fl3728_rs::{
+    Address, Marquee, MatrixDimensions, Orientation, Tile, TiledDisplay, IS31FL3728,
+};
+
+const LEFT: u8 = 0x60;
+const RIGHT: u8 = 0x61;
+
+#[test]
+fn text_comes_from_the_right_edge() {
+    let mut mock = Mock::new(&[
+        Transaction::write(LEFT, vec![0x00, 0b11]),
+        Transaction::write(LEFT, vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x11]),
+        Transaction::write(LEFT, vec![0x0C, 0]),
+        Transaction::write(LEFT, vec![0x0A, 0x11, 0x1F]),
+        Transaction::write(LEFT, vec![0x0C, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    let mut marquee: Marquee<8> = Marquee::new("I", &FONT_3X5).unwrap().step_period(50);
+
+    assert!(driver.marquee_tick(&mut marquee, 0).unwrap());
+    assert!(driver.marquee_tick(&mut marquee, 20).unwrap());
+    assert!(driver.marquee_tick(&mut marquee, 50).unwrap());
+    assert!(!marquee.is_finished(11));
+
+    mock.done();
+}
+
+#[test]
+fn text_is_chained_across_tiles() {
+    let mut mock = Mock::new(&[
+        Transaction::write(LEFT, vec![0x00, 0b00]),
+        Transaction::write(RIGHT, vec![0x00, 0b00]),
+        Transaction::write(LEFT, vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(RIGHT, vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(LEFT, vec![0x0C, 0]),
+        Transaction::write(RIGHT, vec![0x0C, 0]),
+        Transaction::write(RIGHT, vec![0x08, 0x82]),
+        Transaction::write(LEFT, vec![0x0C, 0]),
+        Transaction::write(RIGHT, vec![0x0C, 0]),
+    ]);
+
+    let left = IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    let right = IS31FL3728::new(mock.clone(), Address::Scl, MatrixDimensions::M8x8, false).unwrap();
+    let mut display = TiledDisplay::new(
+        [left, right],
+        [
+            Tile::new(0, 0, Orientation::Rotate0),
+            Tile::new(1, 0, Orientation::Rotate0),
+        ],
+    );
+    let mut marquee: Marquee<8> = Marquee::new("I", &FONT_5X7).unwrap();
+
+    assert!(display.marquee_step(&mut marquee).unwrap());
+    assert!(display.marquee_step(&mut marquee).unwrap());
+
+    mock.done();
+}
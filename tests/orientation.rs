@@ -0,0 +1,193 @@
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::{geometry, Address, DriverError, MatrixDimensions, Orientation, IS31FL3728};
+
+const ADDRESS: u8 = 0x60;
+
+#[test]
+fn rotated_bitmap_of_non_square_matrix() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b11]),
+        Transaction::write(ADDRESS, vec![0x01, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    driver.set_orientation(Orientation::Rotate90).unwrap();
+    assert_eq!((driver.width(), driver.height()), (5, 11));
+
+    let mut picture = [0u16; 11];
+    picture[0] = 0b00001;
+    picture[10] = 0b10000;
+    driver.draw_bitmap(&picture).unwrap();
+
+    // a rotated column has 11 pixels and doesn't fit to a byte
+    let result = driver.draw(&[0; 5]);
+    assert!(matches!(result, Err(DriverError::UnsupportedMode)));
+
+    mock.done();
+}
+
+#[test]
+fn mirrored_pixels() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0x80]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x01, 0x01]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    driver
+        .set_orientation(Orientation::MirrorHorizontal)
+        .unwrap();
+    driver.set_pixel(0, 0, true);
+    driver.flush().unwrap();
+
+    driver.set_orientation(Orientation::MirrorVertical).unwrap();
+    driver.set_pixel(0, 0, true);
+    assert!(driver.get_pixel(7, 7));
+    driver.flush().unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn raw_columns_ignore_orientation() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x01, 0b1000_0000]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x01, 0x80, 0x40]),
+        Transaction::write(ADDRESS, vec![0x01, 0x01, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    driver.set_orientation(Orientation::Rotate180).unwrap();
+    driver.draw_column(1, 0b1000_0000).unwrap();
+    driver.write_columns(1, &[0x80, 0x40]).unwrap();
+    driver.write_frame(&[0x01, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+    driver.update().unwrap();
+
+    // the bottom left LED of the wired matrix is the top right pixel of the rotated one
+    assert!(driver.get_pixel(7, 0));
+
+    mock.done();
+}
+
+#[test]
+fn rotated_non_square_frame_is_unsupported() {
+    let mut mock = Mock::new(&[Transaction::write(ADDRESS, vec![0x00, 0b01])]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M7x9, false).unwrap();
+    driver.set_orientation(Orientation::Rotate270).unwrap();
+    // a rotated column doesn't fit to a byte, frames of any length are rejected
+    let result = driver.draw(&[0; 7]);
+    assert!(matches!(result, Err(DriverError::UnsupportedMode)));
+    let result = driver.draw(&[0; 9]);
+    assert!(matches!(result, Err(DriverError::UnsupportedMode)));
+
+    mock.done();
+}
+
+#[test]
+fn static_non_square_geometry_rejects_rotation() {
+    for orientation in [Orientation::Rotate90, Orientation::Rotate270] {
+        let mut mock = Mock::new(&[
+            Transaction::write(ADDRESS, vec![0x00, 0b11]),
+            Transaction::write(ADDRESS, vec![0x01, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Transaction::write(ADDRESS, vec![0x0C, 0]),
+        ]);
+
+        let mut driver =
+            IS31FL3728::new(mock.clone(), Address::Gnd, geometry::M5x11, false).unwrap();
+        let result = driver.set_orientation(orientation);
+        assert!(matches!(result, Err(DriverError::UnsupportedMode)));
+        assert_eq!(driver.orientation(), Orientation::Rotate0);
+
+        // typed frame still has the shape of the matrix
+        let mut picture = [0; 11];
+        picture[0] = 0b0001_0000;
+        driver.draw(&picture).unwrap();
+
+        mock.done();
+    }
+}
+
+#[test]
+fn static_geometry_allows_half_turn_and_square_rotation() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b10]),
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+    ]);
+
+    let mut driver = IS31FL3728::new(mock.clone(), Address::Gnd, geometry::M6x10, false).unwrap();
+    for orientation in [
+        Orientation::Rotate180,
+        Orientation::MirrorHorizontal,
+        Orientation::MirrorVertical,
+    ] {
+        driver.set_orientation(orientation).unwrap();
+    }
+    assert_eq!((driver.width(), driver.height()), (10, 6));
+
+    let mut driver = IS31FL3728::new(mock.clone(), Address::Gnd, geometry::M8x8, false).unwrap();
+    driver.set_orientation(Orientation::Rotate90).unwrap();
+    driver.set_orientation(Orientation::Rotate270).unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn mirrored_non_square_frame() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b10]),
+        Transaction::write(ADDRESS, vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0b0010_0000]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+
+    let mut driver = IS31FL3728::new(mock.clone(), Address::Gnd, geometry::M6x10, false).unwrap();
+    driver
+        .set_orientation(Orientation::MirrorHorizontal)
+        .unwrap();
+    let mut picture = [0; 10];
+    picture[0] = 0b0010_0000;
+    driver.draw(&picture).unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn byte_rows_dont_fit_wide_matrix() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b11]),
+        Transaction::write(ADDRESS, vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    let result = driver.draw_bitmap(&[0xFFu8; 5]);
+    assert_eq!(result, Err(DriverError::BitmapRowTooNarrow(8, 11)));
+
+    // rows of the rotated matrix have 5 pixels
+    driver.set_orientation(Orientation::Rotate90).unwrap();
+    assert!(driver.draw_bitmap(&[0u8; 11]).is_ok());
+
+    mock.done();
+}
+
+#[test]
+fn point_outside_of_matrix() {
+    let rotated = Orientation::Rotate90;
+    assert_eq!(rotated.to_matrix(4, 10, 11, 5), Some((10, 0)));
+    assert_eq!(rotated.to_matrix(5, 0, 11, 5), None);
+    assert_eq!(rotated.to_matrix(0, 11, 11, 5), None);
+    assert_eq!(Orientation::Rotate180.to_matrix(8, 0, 8, 8), None);
+    assert_eq!(Orientation::Rotate0.to_matrix(0, 0, 0, 0), None);
+}
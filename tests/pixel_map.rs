@@ -0,0 +1,129 @@
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::{Address, DriverError, MatrixDimensions, PixelMap, IS31FL3728};
+
+const ADDRESS: u8 = 0x60;
+
+#[test]
+fn columns_and_bits_are_remapped() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(
+            ADDRESS,
+            vec![0x01, 0, 0, 0, 0, 0, 0, 0b1000_0000, 0b0000_0011],
+        ),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x08, 0b0000_0111]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    driver
+        .set_pixel_map(PixelMap {
+            reversed_bits: true,
+            ..PixelMap::REVERSED_COLUMNS_8
+        })
+        .unwrap();
+
+    driver.draw(&[0b1100_0000, 0b0000_0001]).unwrap();
+    assert!(driver.get_pixel(0, 1));
+
+    driver.send_column(1, 0b1110_0000).unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn invalid_pixel_map_is_rejected() {
+    let mut mock = Mock::new(&[Transaction::write(ADDRESS, vec![0x00, 0b11])]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    // 5x11 matrix doesn't have rows 5-7
+    let result = driver.set_pixel_map(PixelMap::REVERSED_ROWS_8);
+    assert!(matches!(result, Err(DriverError::InvalidPixelMap)));
+    assert!(driver.set_pixel_map(PixelMap::REVERSED_BITS).is_ok());
+
+    let mut pixel_map = PixelMap::IDENTITY;
+    pixel_map.columns[10] = 0;
+    let result = driver.set_pixel_map(pixel_map);
+    assert!(matches!(result, Err(DriverError::InvalidPixelMap)));
+
+    mock.done();
+}
+
+/// Pixels of the first column from the top row to the bottom one, each pixel is flushed alone.
+fn column_bits(dimensions: MatrixDimensions, configuration: u8, expected: &[u8]) {
+    let mut cleared = vec![0x01];
+    cleared.resize(dimensions.columns_count() as usize + 1, 0);
+    let mut expectations = vec![
+        Transaction::write(ADDRESS, vec![0x00, configuration]),
+        Transaction::write(ADDRESS, cleared),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ];
+    for column in expected {
+        expectations.push(Transaction::write(ADDRESS, vec![0x01, *column]));
+        expectations.push(Transaction::write(ADDRESS, vec![0x01, 0]));
+    }
+    let mut mock = Mock::new(&expectations);
+
+    let mut driver = IS31FL3728::new(mock.clone(), Address::Gnd, dimensions, false).unwrap();
+    driver.clear().unwrap();
+    for row in 0..dimensions.rows_count() {
+        driver.set_pixel(0, row, true);
+        driver.send_changes().unwrap();
+        driver.set_pixel(0, row, false);
+        driver.send_changes().unwrap();
+    }
+
+    mock.done();
+}
+
+// the chip ignores older bits of columns when there are less than 8 rows
+
+#[test]
+fn row_bits_8x8() {
+    let expected = [0x80, 0x40, 0x20, 0x10, 0x08, 0x04, 0x02, 0x01];
+    column_bits(MatrixDimensions::M8x8, 0b00, &expected);
+}
+
+#[test]
+fn row_bits_7x9() {
+    let expected = [0x40, 0x20, 0x10, 0x08, 0x04, 0x02, 0x01];
+    column_bits(MatrixDimensions::M7x9, 0b01, &expected);
+}
+
+#[test]
+fn row_bits_6x10() {
+    let expected = [0x20, 0x10, 0x08, 0x04, 0x02, 0x01];
+    column_bits(MatrixDimensions::M6x10, 0b10, &expected);
+}
+
+#[test]
+fn row_bits_5x11() {
+    let expected = [0x10, 0x08, 0x04, 0x02, 0x01];
+    column_bits(MatrixDimensions::M5x11, 0b11, &expected);
+    assert_eq!(MatrixDimensions::M5x11.row_mask(0), Some(0b0001_0000));
+    assert_eq!(MatrixDimensions::M5x11.row_mask(5), None);
+    assert_eq!(MatrixDimensions::M8x8.row_mask(u8::MAX), None);
+}
+
+#[test]
+fn remapped_rows_of_5x11() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b11]),
+        Transaction::write(ADDRESS, vec![0x01, 0b0000_0001]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    driver
+        .set_pixel_map(PixelMap {
+            rows: [4, 3, 2, 1, 0, 5, 6, 7],
+            ..PixelMap::IDENTITY
+        })
+        .unwrap();
+    driver.draw_column(1, 0b0001_0000).unwrap();
+
+    mock.done();
+}
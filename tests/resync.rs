@@ -0,0 +1,131 @@
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::{
+    Address, LightingIntensity, MatrixDimensions, ResyncPolicy, WriteMode, IS31FL3728,
+};
+
+const ADDRESS: u8 = 0x60;
+
+#[test]
+fn reset_restores_defaults() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b0000_0110]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1000]),
+        Transaction::write(ADDRESS, vec![0xFF, 0]),
+        Transaction::write(ADDRESS, vec![0x00, 0b10]),
+        // the cache matches the chip again
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1000]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M6x10, true).unwrap();
+    driver.set_intensity(LightingIntensity::C05mA).unwrap();
+    driver.set_pixel(0, 0, true);
+
+    driver.reset().unwrap();
+    assert_eq!(driver.intensity(), LightingIntensity::C40mA);
+    assert!(!driver.audio_mode().input_enabled);
+    assert!(!driver.get_pixel(0, 0));
+    driver.set_intensity(LightingIntensity::C05mA).unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn resync_rewrites_registers_and_frame() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b01]),
+        Transaction::write(ADDRESS, vec![0x01, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1001]),
+        Transaction::write(ADDRESS, vec![0x02, 2]),
+        // resync
+        Transaction::write(ADDRESS, vec![0x00, 0b01]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1001]),
+        Transaction::write(ADDRESS, vec![0x0F, 0]),
+        Transaction::write(ADDRESS, vec![0x01, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x01, 1, 2, 0, 0, 0, 0, 0, 0, 0]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M7x9, false).unwrap();
+    driver.draw(&[1]).unwrap();
+    driver.set_intensity(LightingIntensity::C10mA).unwrap();
+    driver.send_column(2, 2).unwrap();
+
+    driver.resync().unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn write_after_error_resyncs() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1000]).with_error(ErrorKind::Other),
+        // resync before the next write
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x0D, 0]),
+        Transaction::write(ADDRESS, vec![0x0F, 0]),
+        Transaction::write(ADDRESS, vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1000]),
+        // no more resync
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1001]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    driver.set_resync_policy(ResyncPolicy::AfterError);
+    let _ = driver.set_intensity(LightingIntensity::C05mA);
+    driver.set_intensity(LightingIntensity::C05mA).unwrap();
+    driver.set_intensity(LightingIntensity::C10mA).unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn manual_policy_doesnt_resync() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1000]).with_error(ErrorKind::Other),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1000]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    assert_eq!(driver.resync_policy(), ResyncPolicy::Manual);
+    assert!(driver.set_intensity(LightingIntensity::C05mA).is_err());
+    driver.set_intensity(LightingIntensity::C05mA).unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn resync_sends_columns_one_by_one() {
+    let mut expectations = vec![
+        Transaction::write(ADDRESS, vec![0x00, 0b11]),
+        // resync
+        Transaction::write(ADDRESS, vec![0x00, 0b11]),
+        Transaction::write(ADDRESS, vec![0x0D, 0]),
+        Transaction::write(ADDRESS, vec![0x0F, 0]),
+    ];
+    for update in [true, false] {
+        for column_number in 1..=11 {
+            expectations.push(Transaction::write(ADDRESS, vec![column_number, 0]));
+        }
+        if update {
+            expectations.push(Transaction::write(ADDRESS, vec![0x0C, 0]));
+        }
+    }
+    let mut mock = Mock::new(&expectations);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    driver.set_write_mode(WriteMode::PerColumn);
+    driver.resync().unwrap();
+
+    mock.done();
+}
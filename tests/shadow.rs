@@ -0,0 +1,104 @@
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::{
+    Address, AudioInputGain, LightingIntensity, MatrixDimensions, PixelMap, IS31FL3728,
+};
+
+const ADDRESS: u8 = 0x60;
+
+#[test]
+fn shutdown_is_shadowed() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b0000_0100]),
+        Transaction::write(ADDRESS, vec![0x00, 0b1000_0100]),
+        Transaction::write(ADDRESS, vec![0x00, 0b0000_0100]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, true).unwrap();
+    assert!(!driver.is_shutdown());
+    driver.software_shutdown().unwrap();
+    assert!(driver.is_shutdown());
+    assert_eq!(driver.registers().configuration(), 0b1000_0100);
+    driver.software_on().unwrap();
+    assert!(!driver.is_shutdown());
+
+    mock.done();
+}
+
+#[test]
+fn pending_and_latched_columns() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b11]),
+        Transaction::write(ADDRESS, vec![0x01, 0b1000_0000]),
+        Transaction::write(ADDRESS, vec![0x0C, 0]),
+        Transaction::write(ADDRESS, vec![0x0B, 0b0000_1000]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    assert_eq!(driver.registers().dimensions(), MatrixDimensions::M5x11);
+    driver.draw_column(1, 0b1000_0000).unwrap();
+    driver.send_column(11, 0b0000_1000).unwrap();
+
+    let registers = driver.registers();
+    assert_eq!(registers.pending_columns()[0], 0b1000_0000);
+    assert_eq!(registers.pending_columns()[10], 0b0000_1000);
+    assert_eq!(registers.latched_columns()[0], 0b1000_0000);
+    assert_eq!(registers.latched_columns()[10], 0);
+    assert_eq!(registers.register(0x0B), 0b0000_1000);
+
+    mock.done();
+}
+
+#[test]
+fn lighting_effect_getters() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1100]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0101_1100]),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    assert_eq!(driver.intensity(), LightingIntensity::C40mA);
+    assert_eq!(driver.audio_gain(), AudioInputGain::G00dB);
+
+    driver.set_intensity(LightingIntensity::C25mA).unwrap();
+    driver.set_audio_input_gain(AudioInputGain::G15dB).unwrap();
+    assert_eq!(driver.intensity(), LightingIntensity::C25mA);
+    assert_eq!(driver.audio_gain(), AudioInputGain::G15dB);
+    assert_eq!(driver.registers().lighting_effect(), 0b0101_1100);
+
+    mock.done();
+}
+
+#[test]
+fn failed_write_is_not_shadowed() {
+    let mut mock = Mock::new(&[
+        Transaction::write(ADDRESS, vec![0x00, 0b00]),
+        Transaction::write(ADDRESS, vec![0x0D, 0b0000_1000])
+            .with_error(embedded_hal::i2c::ErrorKind::Other),
+    ]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    assert!(driver.set_intensity(LightingIntensity::C05mA).is_err());
+    assert_eq!(driver.intensity(), LightingIntensity::C40mA);
+
+    mock.done();
+}
+
+#[test]
+fn frame_is_in_wire_order() {
+    let mut mock = Mock::new(&[Transaction::write(ADDRESS, vec![0x00, 0b00])]);
+
+    let mut driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    driver.set_pixel_map(PixelMap::REVERSED_COLUMNS_8).unwrap();
+    driver.set_pixel(0, 0, true);
+    assert_eq!(driver.frame(), &[0, 0, 0, 0, 0, 0, 0, 0b1000_0000]);
+    // nothing is sent yet
+    assert_eq!(driver.registers().pending_columns(), &[0; 11]);
+
+    mock.done();
+}
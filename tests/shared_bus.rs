@@ -0,0 +1,103 @@
+use core::cell::RefCell;
+
+use embedded_hal::i2c::I2c;
+use embedded_hal_bus::i2c::{AtomicDevice, CriticalSectionDevice, RefCellDevice};
+use embedded_hal_bus::util::AtomicCell;
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::{Address, MatrixDimensions, IS31FL3728};
+
+const LEFT: u8 = 0x60;
+const RIGHT: u8 = 0x61;
+const SENSOR: u8 = 0x48;
+
+/// Two matrices are created, draw one frame each and a sensor is read in between.
+fn expectations() -> Vec<Transaction> {
+    vec![
+        Transaction::write(LEFT, vec![0x00, 0b00]),
+        Transaction::write(RIGHT, vec![0x00, 0b11]),
+        Transaction::write(LEFT, vec![0x01, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Transaction::write(LEFT, vec![0x0C, 0]),
+        Transaction::write_read(SENSOR, vec![0x00], vec![0x2A]),
+        Transaction::write(RIGHT, vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x1F]),
+        Transaction::write(RIGHT, vec![0x0C, 0]),
+    ]
+}
+
+fn run<I2C: I2c>(left: I2C, right: I2C, mut sensor: I2C) -> (I2C, I2C) {
+    let mut left = IS31FL3728::new(left, Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    let mut right = IS31FL3728::new(right, Address::Scl, MatrixDimensions::M5x11, false).unwrap();
+
+    left.draw(&[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+
+    let mut temperature = [0u8];
+    sensor
+        .write_read(SENSOR, &[0x00], &mut temperature)
+        .unwrap();
+    assert_eq!(temperature, [0x2A]);
+
+    right.set_pixel(10, 0, true);
+    right.set_pixel(10, 4, true);
+    right.toggle_pixel(10, 1);
+    right.set_pixel(10, 2, true);
+    right.set_pixel(10, 3, true);
+    right.flush().unwrap();
+
+    (left.release(), right.release())
+}
+
+#[test]
+fn ref_cell_device() {
+    let mut mock = Mock::new(&expectations());
+    let bus = RefCell::new(mock.clone());
+
+    run(
+        RefCellDevice::new(&bus),
+        RefCellDevice::new(&bus),
+        RefCellDevice::new(&bus),
+    );
+
+    mock.done();
+}
+
+#[test]
+fn critical_section_device() {
+    let mut mock = Mock::new(&expectations());
+    let bus = critical_section::Mutex::new(RefCell::new(mock.clone()));
+
+    run(
+        CriticalSectionDevice::new(&bus),
+        CriticalSectionDevice::new(&bus),
+        CriticalSectionDevice::new(&bus),
+    );
+
+    mock.done();
+}
+
+#[test]
+fn atomic_device() {
+    let mut mock = Mock::new(&expectations());
+    let bus = AtomicCell::new(mock.clone());
+
+    run(
+        AtomicDevice::new(&bus),
+        AtomicDevice::new(&bus),
+        AtomicDevice::new(&bus),
+    );
+
+    mock.done();
+}
+
+#[test]
+fn release_returns_bus() {
+    let mut mock = Mock::new(&[
+        Transaction::write(LEFT, vec![0x00, 0b00]),
+        Transaction::write(RIGHT, vec![0x0C, 0]),
+    ]);
+
+    let driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    let mut i2c = driver.release();
+    i2c.write(RIGHT, &[0x0C, 0]).unwrap();
+
+    mock.done();
+}
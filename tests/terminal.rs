@@ -0,0 +1,68 @@
+#![cfg(feature = "emulator")]
+
+use is31fl3728_rs::emulator::Emulator;
+use is31fl3728_rs::terminal::{Renderer, Style};
+use is31fl3728_rs::{Address, MatrixDimensions, Orientation, Tile, TiledDisplay, IS31FL3728};
+
+#[test]
+fn matrix_with_status() {
+    let emulator = Emulator::new(0x60);
+    let mut driver = IS31FL3728::new(
+        emulator.clone(),
+        Address::Gnd,
+        MatrixDimensions::M5x11,
+        false,
+    )
+    .unwrap();
+    driver.set_pixel(0, 0, true);
+    driver.set_pixel(10, 4, true);
+    driver.flush().unwrap();
+
+    let renderer = Renderer::new(Style::Ascii);
+    assert_eq!(
+        renderer.render(&emulator),
+        "#..........\n\
+         ...........\n\
+         ...........\n\
+         ...........\n\
+         ..........#\n\
+         0x60 M5x11 40mA (53%) on\n"
+    );
+
+    driver.software_shutdown().unwrap();
+    assert_eq!(
+        Renderer::new(Style::Blocks)
+            .render(&emulator)
+            .lines()
+            .next(),
+        Some("░░░░░░░░░░░░░░░░░░░░░░")
+    );
+}
+
+#[test]
+fn tiles_with_orientation() {
+    let left = Emulator::new(0x60);
+    let right = Emulator::new(0x61);
+    let tiles = [
+        Tile::new(0, 0, Orientation::Rotate0),
+        Tile::new(1, 0, Orientation::Rotate180),
+    ];
+    let mut display = TiledDisplay::new(
+        [
+            IS31FL3728::new(left.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap(),
+            IS31FL3728::new(right.clone(), Address::Scl, MatrixDimensions::M8x8, false).unwrap(),
+        ],
+        tiles,
+    );
+    display.set_pixel(0, 0, true);
+    display.set_pixel(9, 1, true);
+    display.flush().unwrap();
+
+    let text = Renderer::new(Style::Ascii).render_tiles(&[left, right], &tiles);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 10);
+    assert_eq!(lines[0], "#...............");
+    assert_eq!(lines[1], ".........#......");
+    assert_eq!(lines[8], "[0, 0] 0x60 M8x8 40mA (53%) on");
+    assert_eq!(lines[9], "[1, 0] 0x61 M8x8 40mA (53%) on");
+}
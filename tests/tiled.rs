@@ -0,0 +1,124 @@
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, Operation};
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use is31fl3728_rs::{Address, MatrixDimensions, Orientation, Tile, TiledDisplay, IS31FL3728};
+
+const LEFT: u8 = 0x60;
+const RIGHT: u8 = 0x61;
+
+#[test]
+fn pixels_are_split_between_tiles() {
+    let mut mock = Mock::new(&[
+        Transaction::write(LEFT, vec![0x00, 0b00]),
+        Transaction::write(RIGHT, vec![0x00, 0b00]),
+        Transaction::write(LEFT, vec![0x01, 0b1000_0000, 0, 0, 0, 0, 0, 0, 0]),
+        // the right tile is upside down: the last column, the last row
+        Transaction::write(RIGHT, vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0b0000_0001]),
+        Transaction::write(LEFT, vec![0x0C, 0]),
+        Transaction::write(RIGHT, vec![0x0C, 0]),
+        Transaction::write(RIGHT, vec![0x02, 0b0000_0010]),
+        Transaction::write(LEFT, vec![0x0C, 0]),
+        Transaction::write(RIGHT, vec![0x0C, 0]),
+    ]);
+
+    let left = IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    let right = IS31FL3728::new(mock.clone(), Address::Scl, MatrixDimensions::M8x8, false).unwrap();
+    let mut display = TiledDisplay::new(
+        [left, right],
+        [
+            Tile::new(0, 0, Orientation::Rotate0),
+            Tile::new(1, 0, Orientation::Rotate180),
+        ],
+    );
+    assert_eq!((display.width(), display.height()), (16, 8));
+
+    display.set_pixel(0, 0, true);
+    display.set_pixel(8, 0, true);
+    display.flush().unwrap();
+    assert!(display.get_pixel(8, 0));
+
+    display.set_pixel(14, 1, true);
+    display.flush().unwrap();
+
+    mock.done();
+}
+
+#[test]
+fn rotated_tile_swaps_dimensions() {
+    let mut mock = Mock::new(&[
+        Transaction::write(LEFT, vec![0x00, 0b11]),
+        Transaction::write(LEFT, vec![0x01, 0b0001_0000, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(LEFT, vec![0x0C, 0]),
+    ]);
+
+    let driver =
+        IS31FL3728::new(mock.clone(), Address::Gnd, MatrixDimensions::M5x11, false).unwrap();
+    let mut display = TiledDisplay::new([driver], [Tile::new(0, 0, Orientation::Rotate90)]);
+    assert_eq!((display.width(), display.height()), (5, 11));
+
+    // the top right corner of the rotated matrix is the first column and the first row
+    display.set_pixel(4, 0, true);
+    display.flush().unwrap();
+
+    mock.done();
+}
+
+/// Bus of other type, like the second I2C peripheral of a HAL.
+struct OtherBus(Mock);
+
+impl ErrorType for OtherBus {
+    type Error = ErrorKind;
+}
+
+impl I2c for OtherBus {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => self.0.write(address, bytes)?,
+                Operation::Read(buffer) => self.0.read(address, buffer)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn tiles_on_different_buses() {
+    let mut first = Mock::new(&[
+        Transaction::write(LEFT, vec![0x00, 0b00]),
+        Transaction::write(LEFT, vec![0x01, 0b1000_0000, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(LEFT, vec![0x0C, 0]),
+    ]);
+    // both chips have the same address, they are on separate buses
+    let mut second = Mock::new(&[
+        Transaction::write(LEFT, vec![0x00, 0b00]),
+        Transaction::write(LEFT, vec![0x01, 0b0100_0000, 0, 0, 0, 0, 0, 0, 0]),
+        Transaction::write(LEFT, vec![0x0C, 0]),
+    ]);
+
+    let mut first_bus = first.clone();
+    let mut second_bus = OtherBus(second.clone());
+    let first_bus: &mut dyn I2c<Error = ErrorKind> = &mut first_bus;
+    let second_bus: &mut dyn I2c<Error = ErrorKind> = &mut second_bus;
+
+    let top = IS31FL3728::new(first_bus, Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    let bottom = IS31FL3728::new(second_bus, Address::Gnd, MatrixDimensions::M8x8, false).unwrap();
+    let mut display = TiledDisplay::new(
+        [top, bottom],
+        [
+            Tile::new(0, 0, Orientation::Rotate0),
+            Tile::new(0, 1, Orientation::Rotate0),
+        ],
+    );
+    assert_eq!((display.width(), display.height()), (8, 16));
+
+    display.set_pixel(0, 0, true);
+    display.set_pixel(0, 9, true);
+    display.flush().unwrap();
+
+    first.done();
+    second.done();
+}